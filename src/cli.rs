@@ -1,10 +1,11 @@
 use std::path::PathBuf;
+use std::time::Duration;
 
 use clap::{Args, ValueEnum};
 use strum::Display;
 
 use crate::cli_add::AddArguments;
-use crate::cli_build::{BuildArguments, OpenTarget};
+use crate::cli_build::{ArchiveFormat, BuildArguments, BuildOpenTarget, OpenTarget};
 use crate::cli_new::NewArguments;
 use crate::cli_run::RunArguments;
 use crate::cli_test::TestArguments;
@@ -49,6 +50,53 @@ pub enum Command {
         /// Version to install (e.g., '2023.1', '2021.1.0f1').
         #[arg(value_name = "VERSION")]
         version: String,
+
+        /// Install through Unity Hub instead of downloading the editor directly.
+        #[arg(long)]
+        hub: bool,
+
+        /// Build support module to install (e.g., 'android', 'ios', 'webgl'). Can be repeated.
+        /// If the editor is already installed, only the modules are installed.
+        #[arg(short = 'm', long = "module", value_name = "MODULE")]
+        modules: Vec<String>,
+
+        /// Accept the license agreements of the modules to install.
+        #[arg(long)]
+        accept_eula: bool,
+    },
+
+    /// Uninstall a Unity version.
+    #[command()]
+    Uninstall {
+        /// Version to uninstall (e.g., '2021.1.0f1'). A prefix must match a single installed version.
+        #[arg(value_name = "VERSION")]
+        version: String,
+
+        /// Uninstall without asking for confirmation.
+        #[arg(short = 'y', long)]
+        yes: bool,
+    },
+
+    /// Remove installed Unity versions that are not used by any project.
+    #[command()]
+    Gc {
+        /// Directories to recursively search for Unity projects.
+        #[arg(long, value_name = "DIRECTORY", num_args = 1.., required = true, value_hint = clap::ValueHint::DirPath)]
+        roots: Vec<PathBuf>,
+
+        /// Remove the unused versions without asking for confirmation.
+        #[arg(short = 'y', long)]
+        yes: bool,
+
+        /// Only list the unused versions without removing them.
+        #[arg(short = 'n', long)]
+        dry_run: bool,
+
+        /// Remove the unused versions even if the version of some projects cannot be read.
+        ///
+        /// Without it, nothing is removed, because those projects may use one of the versions.
+        #[arg(long)]
+        ignore_unreadable: bool,
     },
 
     /// Display information about a Unity project.
@@ -101,7 +149,93 @@ pub enum Command {
 
     /// Build a Unity project for a specified target platform.
     #[command(visible_alias = "b")]
-    Build(BuildArguments),
+    Build(Box<BuildArguments>),
+
+    /// Package a build output directory into a versioned archive.
+    ///
+    /// The archive is named '<PRODUCT>-<VERSION>-<TARGET>.<FORMAT>', using the product name and
+    /// bundle version from the project settings, and is written next to the output directory.
+    /// Burst debug information and IL2CPP backup folders are not included.
+    #[command()]
+    Archive {
+        /// The build output directory to archive.
+        #[arg(value_name = "OUTPUT_DIR", value_hint = clap::ValueHint::DirPath)]
+        output_dir: PathBuf,
+
+        /// Path to the Unity project directory. Defaults to the current directory.
+        #[arg(
+            short = 'p',
+            long = "project",
+            value_name = "DIRECTORY",
+            value_hint = clap::ValueHint::DirPath,
+            default_value = "."
+        )]
+        project_dir: PathBuf,
+
+        /// The target name used in the archive name. Defaults to the name of the output directory.
+        #[arg(short = 't', long, value_name = "NAME")]
+        target: Option<String>,
+
+        /// The archive format.
+        #[arg(short = 'f', long, value_name = "FORMAT", default_value = "zip")]
+        format: ArchiveFormat,
+
+        /// Put the Burst debug information and IL2CPP backup folders into a separate symbols archive.
+        #[arg(long)]
+        symbols: bool,
+    },
+
+    /// Remove files that ucom left behind in a Unity project.
+    #[command()]
+    Clean {
+        /// Remove the build scripts that were injected for a build, but not removed because the
+        /// build was interrupted ('Assets/Ucom-<UUID>' directories and their meta files).
+        #[arg(long, required = true)]
+        stale_injections: bool,
+
+        /// Show what would be removed without removing it.
+        #[arg(short = 'n', long)]
+        dry_run: bool,
+
+        /// Path to the Unity project directory. Defaults to the current directory.
+        #[arg(value_name = "DIRECTORY", value_hint = clap::ValueHint::DirPath, default_value = ".")]
+        project_dir: PathBuf,
+    },
+
+    /// Show or change the version of a Unity project.
+    ///
+    /// Changing the version updates `bundleVersion`, `AndroidBundleVersionCode` and the iOS
+    /// `buildNumber` in `ProjectSettings/ProjectSettings.asset`. The rest of the file is left as is.
+    #[command()]
+    Version {
+        #[command(subcommand)]
+        action: VersionAction,
+    },
+
+    /// Send a command to the Unity editor that has the project open.
+    ///
+    /// Requires the builder script in the project, add it with `ucom add builder`.
+    #[command()]
+    Editor {
+        #[command(subcommand)]
+        action: EditorAction,
+
+        /// Path to the Unity project directory. Defaults to the current directory.
+        #[arg(
+            short = 'p',
+            long = "project",
+            value_name = "DIRECTORY",
+            value_hint = clap::ValueHint::DirPath,
+            default_value = ".",
+            global = true
+        )]
+        project_dir: PathBuf,
+
+        /// Stop waiting for the editor and cancel the command if it takes longer than this
+        /// (e.g., '90s', '30m'). ucom then exits with code 124.
+        #[arg(long, value_name = "DURATION", value_parser = crate::utils::parse_duration, global = true)]
+        timeout: Option<Duration>,
+    },
 
     /// Run tests within a Unity project.
     #[command(visible_alias = "t")]
@@ -114,6 +248,18 @@ pub enum Command {
     /// Add a helper script or configuration file to the project.
     Add(AddArguments),
 
+    /// Inspect the configuration files that provide defaults for the `build`, `test`, `open`
+    /// and `new` commands.
+    ///
+    /// Settings are read from `ucom.toml` in the project directory and from the user
+    /// configuration at `~/.config/ucom/config.toml`. Command line arguments take precedence
+    /// over environment variables, which take precedence over the project and user configuration.
+    #[command()]
+    Config {
+        #[command(subcommand)]
+        action: ConfigAction,
+    },
+
     /// Manage the download cache for Unity release data.
     ///
     /// By default, cached files expire after one hour. The system will
@@ -201,6 +347,142 @@ pub enum PackagesInfoLevel {
     All,
 }
 
+#[derive(clap::Subcommand)]
+pub enum VersionAction {
+    /// Show the bundle version and the Android and iOS build numbers.
+    Show {
+        /// Path to the Unity project directory. Defaults to the current directory.
+        #[arg(
+            short = 'p',
+            long = "project",
+            value_name = "DIRECTORY",
+            value_hint = clap::ValueHint::DirPath,
+            default_value = "."
+        )]
+        project_dir: PathBuf,
+    },
+
+    /// Set the bundle version and increment the build numbers.
+    Set {
+        /// The new version (e.g., '1.2.0').
+        #[arg(value_name = "VERSION", required_unless_present = "from_git")]
+        version: Option<String>,
+
+        /// Use the version of the latest git tag (e.g., 'v1.2.0').
+        #[arg(long, conflicts_with = "version")]
+        from_git: bool,
+
+        /// Set the Android version code and iOS build number instead of incrementing them.
+        #[arg(short = 'b', long, value_name = "NUMBER")]
+        build_number: Option<u32>,
+
+        /// Show the changes without writing them.
+        #[arg(short = 'n', long)]
+        dry_run: bool,
+
+        /// Path to the Unity project directory. Defaults to the current directory.
+        #[arg(
+            short = 'p',
+            long = "project",
+            value_name = "DIRECTORY",
+            value_hint = clap::ValueHint::DirPath,
+            default_value = "."
+        )]
+        project_dir: PathBuf,
+    },
+
+    /// Increment a part of the bundle version and the build numbers.
+    Bump {
+        /// The part of the version to increment, the parts after it are reset to 0.
+        #[arg(value_enum)]
+        part: VersionPart,
+
+        /// Set the Android version code and iOS build number instead of incrementing them.
+        #[arg(short = 'b', long, value_name = "NUMBER")]
+        build_number: Option<u32>,
+
+        /// Show the changes without writing them.
+        #[arg(short = 'n', long)]
+        dry_run: bool,
+
+        /// Path to the Unity project directory. Defaults to the current directory.
+        #[arg(
+            short = 'p',
+            long = "project",
+            value_name = "DIRECTORY",
+            value_hint = clap::ValueHint::DirPath,
+            default_value = "."
+        )]
+        project_dir: PathBuf,
+    },
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, ValueEnum)]
+pub enum VersionPart {
+    /// Increment the major version, e.g. 1.2.3 becomes 2.0.0.
+    Major,
+    /// Increment the minor version, e.g. 1.2.3 becomes 1.3.0.
+    Minor,
+    /// Increment the patch version, e.g. 1.2.3 becomes 1.2.4.
+    Patch,
+}
+
+#[derive(clap::Subcommand)]
+pub enum EditorAction {
+    /// Import changed assets and recompile scripts (`AssetDatabase.Refresh`).
+    Refresh,
+
+    /// Switch the active build target.
+    SwitchPlatform {
+        /// The target platform.
+        #[arg(value_name = "TARGET")]
+        target: BuildOpenTarget,
+    },
+
+    /// Invoke a static method in the editor.
+    ///
+    /// The method must have no parameters or a single `string[]` parameter, which receives the
+    /// arguments. A value it returns is printed.
+    ExecuteMethod {
+        /// The full name of the type followed by the method name (e.g., 'MyCompany.Tools.Bake').
+        #[arg(value_name = "TYPE.METHOD")]
+        method: String,
+
+        /// Arguments to pass to the method.
+        #[arg(
+            value_name = "ARGS",
+            trailing_var_arg = true,
+            allow_hyphen_values = true
+        )]
+        args: Vec<String>,
+    },
+
+    /// Enter play mode.
+    EnterPlaymode,
+
+    /// Exit play mode.
+    ExitPlaymode,
+
+    /// Save the open scenes.
+    SaveScenes,
+}
+
+#[derive(clap::Subcommand)]
+pub enum ConfigAction {
+    /// Show the resolved settings and where each value comes from.
+    Show {
+        /// Path to the Unity project directory. Defaults to the current directory.
+        #[arg(
+            short = 'p',
+            long = "project",
+            value_name = "DIRECTORY",
+            value_hint = clap::ValueHint::DirPath,
+            default_value = "."
+        )]
+        project_dir: PathBuf,
+    },
+}
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
 pub enum CacheAction {
     /// Remove all cached download files.
@@ -31,6 +31,17 @@ pub struct AddArguments {
     )]
     pub force: bool,
 
+    /// Update the file in the project to the template of this version of ucom, showing the changes.
+    ///
+    /// Updating 'builder-menu' also updates the builder script it depends on.
+    #[arg(
+        long,
+        conflicts_with = "force",
+        conflicts_with = "display_content",
+        conflicts_with = "display_url"
+    )]
+    pub update: bool,
+
     /// Print the content of the selected template file to standard output instead of writing it to the project.
     #[arg(
         short = 'c',
@@ -88,6 +99,13 @@ impl TemplateAsset {
 }
 
 impl UnityTemplateFile {
+    /// Returns the name of the template on the command line.
+    pub fn name(self) -> String {
+        self.to_possible_value()
+            .map(|value| value.get_name().to_string())
+            .unwrap_or_default()
+    }
+
     pub const fn as_asset(self) -> TemplateAsset {
         match self {
             Self::Builder => TemplateAsset {
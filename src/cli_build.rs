@@ -1,18 +1,65 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 
 use clap::{Args, ValueEnum};
 use strum::{AsRefStr, Display};
 
 #[derive(Args)]
 pub struct BuildArguments {
-    /// Target platform to build the project for (e.g., win64, android, webgl). Required.
-    #[arg(value_enum, env = crate::cli::ENV_BUILD_TARGET)]
-    pub target: BuildOpenTarget,
+    /// Target platforms to build the project for (e.g., win64, android, webgl), optionally followed
+    /// by the path to the Unity project directory. The directory defaults to the current directory.
+    ///
+    /// Multiple targets are built one after another.
+    /// A target is required, unless set by the environment (`UCOM_BUILD_TARGET`, multiple targets
+    /// separated by commas), a configuration file or a preset.
+    ///
+    /// [possible targets: win32, win64, macos, linux64, ios, android, webgl]
+    #[arg(
+        id = "target",
+        value_name = "TARGET... [DIRECTORY]",
+        num_args = 0..,
+        value_parser = TargetOrDirectory::parse,
+        value_hint = clap::ValueHint::DirPath
+    )]
+    pub positionals: Vec<TargetOrDirectory>,
+
+    /// The targets to build, taken from the positional arguments.
+    #[arg(skip)]
+    pub targets: Vec<BuildOpenTarget>,
 
-    /// Path to the Unity project directory. Defaults to the current directory.
-    #[arg(value_name = "DIRECTORY", value_hint = clap::ValueHint::DirPath, default_value = ".")]
+    /// The project directory, taken from the positional arguments.
+    #[arg(skip = PathBuf::from("."))]
     pub project_dir: PathBuf,
 
+    /// Build all targets listed in the `all_targets` setting of the configuration.
+    #[arg(long, conflicts_with = "profile")]
+    pub all_targets: bool,
+
+    /// The targets built by --all-targets, only set by a configuration file.
+    #[arg(skip)]
+    pub all_targets_list: Vec<BuildOpenTarget>,
+
+    /// Continue building the remaining targets after a build fails.
+    #[arg(long)]
+    pub keep_going: bool,
+
+    /// Skip the build when nothing changed since the last successful build into the output
+    /// directory.
+    ///
+    /// Compares a fingerprint of 'Assets', 'Packages', 'ProjectSettings', the local packages, the
+    /// editor version and the build arguments, which is stored next to the output directory after
+    /// a successful build.
+    #[arg(long)]
+    pub skip_if_unchanged: bool,
+
+    /// Build a Unity 6 build profile instead of a target platform.
+    ///
+    /// Accepts the name of the profile asset (e.g., 'Android Store') or its path relative to the
+    /// project. Profiles are searched for in the whole `Assets` directory.
+    /// The profile determines the platform, a target is ignored.
+    #[arg(long, value_name = "PROFILE")]
+    pub profile: Option<String>,
+
     /// Specify the exact output directory for the build artifacts.
     /// If not set, defaults to '<PROJECT_DIR>/Builds/<TYPE>/<TARGET>'.
     #[arg(
@@ -89,6 +136,44 @@ pub struct BuildArguments {
     #[arg(short = 'a', long, value_name = "STRING")]
     pub build_args: Option<String>,
 
+    /// Scripting define symbol to add to the build (e.g., 'ENABLE_CHEATS'). Can be repeated.
+    ///
+    /// Requires the ucom build script ('UnityBuilder.cs' or similar) to be present.
+    /// Not supported with --profile, the build profile defines its own symbols.
+    #[arg(long = "define", value_name = "SYMBOL")]
+    pub defines: Vec<String>,
+
+    /// Package the output directory into '<PRODUCT>-<VERSION>-<TARGET>.<FORMAT>' after a
+    /// successful build. The archive is written next to the output directory.
+    ///
+    /// Burst debug information and IL2CPP backup folders are not included.
+    #[arg(long, value_name = "FORMAT")]
+    pub archive: Option<ArchiveFormat>,
+
+    /// Put the Burst debug information and IL2CPP backup folders into a separate symbols archive.
+    #[arg(long, requires = "archive")]
+    pub symbols_archive: bool,
+
+    /// Rerun the build up to N times when it fails because of a transient problem, e.g. a licensing
+    /// server hiccup, another Unity instance having the project open or a network error while
+    /// resolving packages. Failures like compiler errors are never retried.
+    #[arg(long, value_name = "N", default_value_t = 0)]
+    pub retries: u32,
+
+    /// Kill Unity, and the processes it started, if the build takes longer than this
+    /// (e.g., '90s', '30m', '1h30m'). ucom then exits with code 124.
+    ///
+    /// A build in an already open editor is cancelled instead.
+    #[arg(long, value_name = "DURATION", value_parser = crate::utils::parse_duration)]
+    pub timeout: Option<Duration>,
+
+    /// Kill Unity, and the processes it started, if the log has no new output for this long.
+    ///
+    /// Catches an editor that hangs, e.g. on a license dialog or an import deadlock.
+    /// ucom then exits with code 124.
+    #[arg(long, value_name = "DURATION", value_parser = crate::utils::parse_duration)]
+    pub idle_timeout: Option<Duration>,
+
     /// Clean the output directory by removing files not generated by the current build.
     #[arg(short = 'C', long)]
     pub clean: bool,
@@ -126,6 +211,16 @@ pub struct BuildArguments {
     #[arg(short = 'n', long)]
     pub dry_run: bool,
 
+    /// Use the settings of a preset defined in the `[presets.<NAME>]` section of the configuration.
+    ///
+    /// Settings passed on the command line override the values of the preset.
+    #[arg(long, value_name = "NAME")]
+    pub preset: Option<String>,
+
+    /// List the configured presets and the command line each of them resolves to.
+    #[arg(long, conflicts_with = "preset")]
+    pub list_presets: bool,
+
     /// Allow disruptive operations when the Unity editor is already open.
     ///
     /// This flag permits ucom to automatically switch build platforms (which triggers asset reimport)
@@ -139,6 +234,50 @@ pub struct BuildArguments {
     pub args: Option<Vec<String>>,
 }
 
+/// A positional argument of the build command, either a target or the project directory.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TargetOrDirectory {
+    Target(BuildOpenTarget),
+    Directory(PathBuf),
+}
+
+impl TargetOrDirectory {
+    fn parse(value: &str) -> Result<Self, String> {
+        Ok(BuildOpenTarget::from_str(value, true)
+            .map_or_else(|_| Self::Directory(PathBuf::from(value)), Self::Target))
+    }
+}
+
+impl BuildArguments {
+    /// Splits the positional arguments into the targets and the project directory, which must be
+    /// the last positional argument.
+    pub fn split_positionals(&mut self) -> anyhow::Result<()> {
+        self.targets.clear();
+        self.project_dir = PathBuf::from(".");
+
+        for (index, positional) in self.positionals.iter().enumerate() {
+            match positional {
+                TargetOrDirectory::Target(target) => self.targets.push(*target),
+                TargetOrDirectory::Directory(dir)
+                    if index + 1 < self.positionals.len() || is_target_typo(dir) =>
+                {
+                    return Err(anyhow::anyhow!(
+                        "Invalid build target: `{}`",
+                        dir.to_string_lossy()
+                    ));
+                }
+                TargetOrDirectory::Directory(dir) => self.project_dir.clone_from(dir),
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Returns true if the path is not an existing directory and looks like a misspelled target.
+fn is_target_typo(path: &Path) -> bool {
+    !path.exists() && path.components().count() == 1 && !path.to_string_lossy().starts_with('.')
+}
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
 pub enum InjectAction {
     /// Automatically inject the build script if missing, remove it afterward (default).
@@ -157,6 +296,20 @@ pub enum BuildOutputType {
     Debug,
 }
 
+/// The format of a build archive.
+#[derive(Display, Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
+pub enum ArchiveFormat {
+    /// A zip archive.
+    #[value(name = "zip")]
+    #[strum(serialize = "zip")]
+    Zip,
+
+    /// A gzip compressed tar archive, which preserves file permissions and symbolic links.
+    #[value(name = "tar.gz")]
+    #[strum(serialize = "tar.gz")]
+    TarGz,
+}
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
 pub enum BuildMode {
     /// Run Unity in batch mode (no UI, exits after build). Recommended for automation.
@@ -212,7 +365,9 @@ pub enum OpenTarget {
 }
 
 /// Specifies the target platform for the 'build' command.
-#[derive(Display, AsRefStr, Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
+#[derive(
+    Display, AsRefStr, Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, ValueEnum,
+)]
 #[allow(non_camel_case_types)]
 pub enum BuildOpenTarget {
     /// Build for Windows 32-bit.
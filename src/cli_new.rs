@@ -7,9 +7,10 @@ use clap::Args;
 pub struct NewArguments {
     /// Specify the Unity version to use for the new project.
     /// Accepts a full version (e.g., '2022.3.5f1') or a prefix (e.g., '2021', '2022.3').
-    /// A prefix will select the latest installed version matching that prefix. Required.
-    #[arg(short = 'u', long = "unity", value_name = "VERSION", required = true)]
-    pub version_pattern: String,
+    /// A prefix will select the latest installed version matching that prefix.
+    /// Required, unless set by a configuration file.
+    #[arg(short = 'u', long = "unity", value_name = "VERSION")]
+    pub version_pattern: Option<String>,
 
     /// Path and name for the new project directory. This directory must not exist yet. Required.
     #[arg(
@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use clap::Args;
 
 #[derive(Args)]
@@ -12,6 +14,19 @@ pub struct RunArguments {
     #[arg(short = 'w', long)]
     pub wait: bool,
 
+    /// Kill Unity, and the processes it started, if it runs longer than this
+    /// (e.g., '90s', '30m', '1h30m'). Implies --wait. ucom then exits with code 124.
+    #[arg(long, value_name = "DURATION", value_parser = crate::utils::parse_duration)]
+    pub timeout: Option<Duration>,
+
+    /// Kill Unity, and the processes it started, if the log has no new output for this long.
+    ///
+    /// Catches an editor that hangs, e.g. on a license dialog or an import deadlock.
+    /// ucom then exits with code 124.
+    /// Requires the '-logFile <FILE>' Unity argument.
+    #[arg(long, value_name = "DURATION", value_parser = crate::utils::parse_duration)]
+    pub idle_timeout: Option<Duration>,
+
     /// Suppress informational messages from ucom before launching Unity.
     #[arg(short = 'q', long)]
     pub quiet: bool,
@@ -1,4 +1,5 @@
 use std::path::PathBuf;
+use std::time::Duration;
 
 use clap::{Args, ValueEnum};
 use strum::{AsRefStr, Display};
@@ -62,6 +63,26 @@ pub struct TestArguments {
     #[arg(long, value_name = "LIST")]
     pub assemblies: Option<String>,
 
+    /// Rerun the tests up to N times when it fails because of a transient problem, e.g. a licensing
+    /// server hiccup, another Unity instance having the project open or a network error while
+    /// resolving packages. Failures like compiler errors are never retried.
+    #[arg(long, value_name = "N", default_value_t = 0)]
+    pub retries: u32,
+
+    /// Kill Unity, and the processes it started, if the test run takes longer than this
+    /// (e.g., '90s', '30m', '1h30m'). ucom then exits with code 124.
+    ///
+    /// A test run in an already open editor is cancelled instead.
+    #[arg(long, value_name = "DURATION", value_parser = crate::utils::parse_duration)]
+    pub timeout: Option<Duration>,
+
+    /// Kill Unity, and the processes it started, if the log has no new output for this long.
+    ///
+    /// Catches an editor that hangs, e.g. on a license dialog or an import deadlock.
+    /// ucom then exits with code 124.
+    #[arg(long, value_name = "DURATION", value_parser = crate::utils::parse_duration)]
+    pub idle_timeout: Option<Duration>,
+
     /// Suppress informational messages from ucom during the test execution setup.
     #[arg(short = 'q', long)]
     pub quiet: bool,
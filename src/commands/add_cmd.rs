@@ -1,9 +1,16 @@
-use std::path::PathBuf;
+use std::fs;
+use std::path::{Path, PathBuf};
 
 use anyhow::anyhow;
+use similar::{ChangeTag, TextDiff};
+use yansi::Paint;
 
 use crate::cli_add::{AddArguments, AssetSource, UnityTemplateFile};
-use crate::commands::{INDENT, PERSISTENT_BUILD_SCRIPT_ROOT, add_file_to_project};
+use crate::commands::builder_script::is_same_script;
+use crate::commands::{
+    INDENT, PERSISTENT_BUILD_SCRIPT_ROOT, add_file_to_project, create_file, template_content_for,
+};
+use crate::style_definitions::{ERROR, OK, UNSTYLED};
 use crate::unity::project::ProjectPath;
 use crate::utils::path_ext::PlatformConsistentPathExt;
 
@@ -29,6 +36,14 @@ pub fn add_to_project(args: &AddArguments) -> anyhow::Result<()> {
         UnityTemplateFile::GitIgnore | UnityTemplateFile::GitAttributes => PathBuf::default(),
     };
 
+    if args.update {
+        if args.template == UnityTemplateFile::BuilderMenu {
+            // The build menu depends on the builder script.
+            update_file_in_project(&project, &destination_dir, UnityTemplateFile::Builder)?;
+        }
+        return update_file_in_project(&project, &destination_dir, args.template);
+    }
+
     let full_path = project
         .join(&destination_dir)
         .join(args.template.as_asset().filename);
@@ -56,3 +71,64 @@ pub fn add_to_project(args: &AddArguments) -> anyhow::Result<()> {
 
     add_file_to_project(project, destination_dir, args.template)
 }
+
+/// Replaces the file in the project with the template, showing the changes.
+fn update_file_in_project(
+    project: &ProjectPath,
+    destination_dir: &Path,
+    template: UnityTemplateFile,
+) -> anyhow::Result<()> {
+    let local_path = destination_dir.join(template.as_asset().filename);
+    let full_path = project.join(&local_path);
+
+    if !full_path.exists() {
+        return add_file_to_project(project, destination_dir, template);
+    }
+
+    let installed = fs::read_to_string(&full_path)?;
+    let content = template_content_for(project, template)?;
+
+    if is_same_script(&installed, &content) {
+        println!(
+            "{INDENT}Already up to date: {}",
+            local_path.normalized_display()
+        );
+        return Ok(());
+    }
+
+    print_diff(
+        &installed,
+        &content,
+        &local_path.normalized_display().to_string(),
+    );
+    create_file(&full_path, &content)?;
+    println!("{INDENT}Updated: {}", local_path.normalized_display());
+    Ok(())
+}
+
+/// Prints the changes between the installed file and the template as a unified diff.
+fn print_diff(installed: &str, template: &str, file_name: &str) {
+    // Line endings are not shown as changes.
+    let installed = installed.replace("\r\n", "\n");
+    let template = template.replace("\r\n", "\n");
+    let diff = TextDiff::from_lines(&installed, &template);
+
+    println!("{}", format!("--- {file_name} (installed)").bold());
+    println!("{}", format!("+++ {file_name} (template)").bold());
+
+    for hunk in diff.unified_diff().context_radius(3).iter_hunks() {
+        println!("{}", hunk.header().paint(UNSTYLED).dim());
+        for change in hunk.iter_changes() {
+            let (sign, style) = match change.tag() {
+                ChangeTag::Delete => ('-', ERROR),
+                ChangeTag::Insert => ('+', OK),
+                ChangeTag::Equal => (' ', UNSTYLED),
+            };
+            print!("{}", format!("{sign}{change}").paint(style));
+            if change.missing_newline() {
+                println!();
+            }
+        }
+    }
+    println!();
+}
@@ -0,0 +1,340 @@
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, anyhow};
+use chrono::{DateTime, Datelike, Local, Timelike};
+use flate2::Compression;
+use flate2::write::GzEncoder;
+use itertools::Itertools;
+use path_absolutize::Absolutize;
+use walkdir::WalkDir;
+use yansi::Paint;
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+use crate::cli_build::ArchiveFormat;
+use crate::commands::build_cmd::is_do_not_ship_dir;
+use crate::unity::{ProjectPath, ProjectSettings};
+use crate::utils::format_size;
+use crate::utils::path_ext::PlatformConsistentPathExt;
+use crate::utils::status_line::MessageType;
+
+/// Runs the archive command.
+pub fn archive_build_output(
+    output_dir: &Path,
+    project_dir: &Path,
+    target: Option<&str>,
+    format: ArchiveFormat,
+    symbols: bool,
+) -> anyhow::Result<()> {
+    let project = ProjectPath::try_from(project_dir)?;
+    let output_dir = output_dir.absolutize()?;
+
+    // The default output directories are named after the target, e.g. `Builds/Release/Win64`.
+    let target = match target {
+        Some(target) => target.to_string(),
+        None => output_dir
+            .file_name()
+            .ok_or_else(|| anyhow!("Cannot determine the target, use --target to set it"))?
+            .to_string_lossy()
+            .to_string(),
+    };
+
+    archive_output(&project, &output_dir, &target, format, symbols)?;
+    Ok(())
+}
+
+/// Packages a build output directory into an archive named after the project and target.
+///
+/// The archive is written next to the output directory. Burst debug information and IL2CPP backup
+/// folders are left out, or put into a separate symbols archive if `symbols` is set.
+/// Returns the paths of the written archives.
+pub fn archive_output(
+    project: &ProjectPath,
+    output_dir: &Path,
+    target: &str,
+    format: ArchiveFormat,
+    symbols: bool,
+) -> anyhow::Result<Vec<PathBuf>> {
+    if !output_dir.is_dir() {
+        return Err(anyhow!(
+            "Output directory does not exist: `{}`",
+            output_dir.normalized_display()
+        ));
+    }
+
+    let settings = ProjectSettings::from_project(project)
+        .context("Cannot read the product name and version from the project settings")?;
+    let base_name = sanitize_file_name(&format!(
+        "{}-{}-{}",
+        settings.product_name, settings.bundle_version, target
+    ));
+
+    let archive_dir = output_dir
+        .parent()
+        .filter(|p| !p.as_os_str().is_empty())
+        .unwrap_or_else(|| Path::new("."));
+
+    let (symbol_dirs, files): (Vec<_>, Vec<_>) = fs::read_dir(output_dir)?
+        .flatten()
+        .map(|de| de.path())
+        .partition(|p| is_do_not_ship_dir(p));
+
+    let mut archives = Vec::new();
+
+    let archive_path = archive_dir.join(format!("{base_name}.{format}"));
+    write_archive(&archive_path, output_dir, &files, format)?;
+    print_archive(&archive_path);
+    archives.push(archive_path);
+
+    if symbols && !symbol_dirs.is_empty() {
+        let symbols_path = archive_dir.join(format!("{base_name}-symbols.{format}"));
+        write_archive(&symbols_path, output_dir, &symbol_dirs, format)?;
+        print_archive(&symbols_path);
+        archives.push(symbols_path);
+    }
+
+    Ok(archives)
+}
+
+fn print_archive(path: &Path) {
+    let size = fs::metadata(path).map(|md| md.len()).unwrap_or_default();
+    MessageType::print_line(
+        "Archived",
+        format!(
+            "{} ({})",
+            path.normalized_display(),
+            format_size(size).dim()
+        ),
+        MessageType::Ok,
+    );
+}
+
+/// Writes the given files and directories, which are all inside `root`, to an archive.
+/// An existing archive is replaced.
+fn write_archive(
+    archive_path: &Path,
+    root: &Path,
+    entries: &[PathBuf],
+    format: ArchiveFormat,
+) -> anyhow::Result<()> {
+    let mut paths = entries
+        .iter()
+        .flat_map(|entry| WalkDir::new(entry).follow_links(false))
+        .collect::<Result<Vec<_>, _>>()?;
+    paths.sort_by(|a, b| a.path().cmp(b.path()));
+
+    let file = File::create(archive_path).with_context(|| {
+        format!(
+            "Cannot create archive `{}`",
+            archive_path.normalized_display()
+        )
+    })?;
+    let writer = BufWriter::new(file);
+
+    let result = match format {
+        ArchiveFormat::Zip => write_zip(writer, root, &paths),
+        ArchiveFormat::TarGz => write_tar_gz(writer, root, &paths),
+    };
+
+    result.inspect_err(|_| {
+        let _ = fs::remove_file(archive_path);
+    })
+}
+
+fn write_zip(
+    writer: impl Write + io::Seek,
+    root: &Path,
+    entries: &[walkdir::DirEntry],
+) -> anyhow::Result<()> {
+    let mut zip = ZipWriter::new(writer);
+
+    for entry in entries {
+        let name = archive_name(root, entry.path())?;
+        let metadata = entry.metadata()?;
+        let mut options = SimpleFileOptions::default()
+            .compression_method(CompressionMethod::Deflated)
+            .large_file(metadata.len() >= u32::MAX as u64)
+            .last_modified_time(zip_date_time(&metadata));
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            options = options.unix_permissions(metadata.permissions().mode());
+        }
+
+        if entry.path_is_symlink() {
+            let link_target = fs::read_link(entry.path())?;
+            zip.add_symlink(name, link_target.to_string_lossy(), options)?;
+        } else if entry.file_type().is_dir() {
+            zip.add_directory(name, options)?;
+        } else {
+            zip.start_file(name, options)?;
+            io::copy(&mut File::open(entry.path())?, &mut zip)?;
+        }
+    }
+
+    zip.finish()?.flush()?;
+    Ok(())
+}
+
+fn write_tar_gz(
+    writer: impl Write,
+    root: &Path,
+    entries: &[walkdir::DirEntry],
+) -> anyhow::Result<()> {
+    let mut tar = tar::Builder::new(GzEncoder::new(writer, Compression::default()));
+    tar.follow_symlinks(false);
+
+    for entry in entries {
+        let name = archive_name(root, entry.path())?;
+        if entry.file_type().is_dir() && !entry.path_is_symlink() {
+            tar.append_dir(name, entry.path())?;
+        } else {
+            tar.append_path_with_name(entry.path(), name)?;
+        }
+    }
+
+    tar.into_inner()?.finish()?.flush()?;
+    Ok(())
+}
+
+/// Returns the modification time of the file in the local time zone, as zip archives store it.
+fn zip_date_time(metadata: &fs::Metadata) -> zip::DateTime {
+    metadata
+        .modified()
+        .ok()
+        .map(DateTime::<Local>::from)
+        .and_then(|t| {
+            zip::DateTime::from_date_and_time(
+                u16::try_from(t.year()).ok()?,
+                t.month() as u8,
+                t.day() as u8,
+                t.hour() as u8,
+                t.minute() as u8,
+                t.second() as u8,
+            )
+            .ok()
+        })
+        .unwrap_or_default()
+}
+
+/// Returns the name of the entry in the archive, relative to the root and with `/` separators.
+fn archive_name(root: &Path, path: &Path) -> anyhow::Result<String> {
+    let relative_path = path.strip_prefix(root)?;
+    Ok(relative_path
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .join("/"))
+}
+
+/// Replaces characters that are not allowed in file names on any platform.
+fn sanitize_file_name(name: &str) -> String {
+    name.chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+            c if c.is_control() => '_',
+            c => c,
+        })
+        .collect()
+}
+
+//
+// Tests
+//
+
+#[cfg(test)]
+mod archive_cmd_tests {
+    use super::*;
+    use std::io::Read;
+
+    /// Creates an output directory with a file and a nested directory, returns its entries.
+    fn create_output_dir(dir: &Path) -> Vec<PathBuf> {
+        let output_dir = dir.join("Win64");
+        fs::create_dir_all(output_dir.join("Game_Data/Managed")).unwrap();
+        fs::write(output_dir.join("Game.exe"), "game").unwrap();
+        fs::write(
+            output_dir.join("Game_Data/Managed/Assembly.dll"),
+            "assembly",
+        )
+        .unwrap();
+        vec![output_dir.join("Game.exe"), output_dir.join("Game_Data")]
+    }
+
+    #[test]
+    fn test_zip_round_trip() {
+        let dir = std::env::temp_dir().join(format!("ucom-archive-{}", uuid::Uuid::new_v4()));
+        let entries = create_output_dir(&dir);
+        let archive_path = dir.join("Game-1.0-Win64.zip");
+
+        write_archive(
+            &archive_path,
+            &dir.join("Win64"),
+            &entries,
+            ArchiveFormat::Zip,
+        )
+        .unwrap();
+
+        let mut zip = zip::ZipArchive::new(File::open(&archive_path).unwrap()).unwrap();
+        let mut content = String::new();
+        zip.by_name("Game_Data/Managed/Assembly.dll")
+            .unwrap()
+            .read_to_string(&mut content)
+            .unwrap();
+        assert_eq!(content, "assembly");
+        assert!(zip.by_name("Game.exe").is_ok());
+        assert!(zip.by_name("Game_Data/").is_ok());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_tar_gz_round_trip() {
+        let dir = std::env::temp_dir().join(format!("ucom-archive-{}", uuid::Uuid::new_v4()));
+        let entries = create_output_dir(&dir);
+        let archive_path = dir.join("Game-1.0-Win64.tar.gz");
+
+        write_archive(
+            &archive_path,
+            &dir.join("Win64"),
+            &entries,
+            ArchiveFormat::TarGz,
+        )
+        .unwrap();
+
+        let gz = flate2::read::GzDecoder::new(File::open(&archive_path).unwrap());
+        let mut files = Vec::new();
+        for entry in tar::Archive::new(gz).entries().unwrap() {
+            let mut entry = entry.unwrap();
+            if entry.header().entry_type().is_file() {
+                let mut content = String::new();
+                entry.read_to_string(&mut content).unwrap();
+                files.push((
+                    entry.path().unwrap().to_string_lossy().into_owned(),
+                    content,
+                ));
+            }
+        }
+        assert_eq!(
+            files,
+            [
+                ("Game.exe".to_string(), "game".to_string()),
+                (
+                    "Game_Data/Managed/Assembly.dll".to_string(),
+                    "assembly".to_string()
+                ),
+            ]
+        );
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_sanitize_file_name() {
+        assert_eq!(
+            sanitize_file_name("My Game: Deluxe-1.0/2-Win64"),
+            "My Game_ Deluxe-1.0_2-Win64"
+        );
+    }
+}
@@ -1,48 +1,182 @@
+use std::fmt::{Display, Formatter};
 use std::fs;
 use std::fs::File;
 use std::io::{BufRead, BufReader};
 use std::path::{Path, PathBuf};
 use std::process::Command;
-use std::time::Duration;
 
+use crate::cli::ENV_BUILD_TARGET;
 use crate::cli_add::UnityTemplateFile;
-use crate::cli_build::{BuildArguments, BuildMode, BuildOptions, BuildScriptTarget, InjectAction};
+use crate::cli_build::{
+    BuildArguments, BuildMode, BuildOpenTarget, BuildOptions, BuildScriptTarget, InjectAction,
+};
+use crate::commands::archive_cmd::archive_output;
+use crate::commands::build_fingerprint::BuildFingerprint;
+use crate::commands::build_manifest::BuildManifest;
+use crate::commands::builder_script::warn_outdated_scripts;
+use crate::commands::editor_ipc::{
+    BuildCommand, EditorChannel, EditorCommand, EditorMessage, ProgressEvent,
+};
 use crate::commands::{
-    PERSISTENT_BUILD_SCRIPT_ROOT, ProjectSetup, TimeDeltaExt, UnityCommandBuilder,
-    add_file_to_project, check_version_issues,
+    AUTO_BUILD_SCRIPT_ROOT, INDENT, PERSISTENT_BUILD_SCRIPT_ROOT, ProjectSetup, TimeDeltaExt,
+    UnityCommandBuilder, add_file_to_project, check_version_issues, run_with_retries,
 };
+use crate::config::{Config, Configurable, PROJECT_CONFIG_FILE, apply_loaded_config};
+use crate::style_definitions::{ERROR, OK, UNSTYLED};
+use crate::unity::diagnostics::{Diagnostic, report_diagnostics, report_log_errors};
 use crate::unity::{
-    ProjectPath, build_command_line, is_unity_editor_running, wait_with_log_output,
-    wait_with_stdout,
+    ProjectPath, Timeouts, build_command_line, wait_with_log_output, wait_with_stdout,
 };
+use crate::utils::interrupt::CleanupGuard;
 use crate::utils::path_ext::PlatformConsistentPathExt;
 use crate::utils::status_line::{MessageType, StatusLine};
+use crate::utils::{dir_size, format_size};
 use anyhow::{Context, Result, anyhow};
 use chrono::Utc;
+use clap::{ArgMatches, FromArgMatches};
 use itertools::Itertools;
 use path_absolutize::Absolutize;
-use serde::{Deserialize, Serialize};
+use serde::Deserialize;
 use uuid::Uuid;
+use yansi::Paint;
 
-const AUTO_BUILD_SCRIPT_ROOT: &str = "Assets/Ucom";
-
-/// Runs the build command.
+/// Runs the build command, building multiple targets one after another.
 pub fn build_project(arguments: &BuildArguments) -> anyhow::Result<()> {
-    let start_time = Utc::now();
     let setup = ProjectSetup::new(&arguments.project_dir)?;
+    let selections = arguments.build_selections(&setup)?;
+
+    if selections.len() == 1 || arguments.dry_run {
+        for target in &selections {
+            build_target(arguments, &setup, target)?;
+        }
+        return Ok(());
+    }
+
+    let mut summaries = Vec::with_capacity(selections.len());
+    let mut stopped = false;
+    for target in &selections {
+        if stopped {
+            summaries.push(BuildSummary::skipped(target));
+            continue;
+        }
+
+        let start_time = Utc::now();
+        let result = build_target(arguments, &setup, target);
+        let duration = Utc::now().signed_duration_since(start_time).as_seconds();
+
+        if let Err(e) = &result {
+            MessageType::print_line("Error", format!("{e:#}"), MessageType::Error);
+            stopped = !arguments.keep_going;
+        }
+
+        let output_size = arguments
+            .output_path(&setup.project, target)
+            .ok()
+            .filter(|path| result.is_ok() && path.exists())
+            .map(|path| dir_size(&path));
+
+        summaries.push(BuildSummary {
+            target: target.to_string(),
+            status: result.unwrap_or(BuildStatus::Failed),
+            duration: Some(duration),
+            output_size,
+        });
+    }
+
+    print_build_summary(&summaries);
+
+    let count = |status| summaries.iter().filter(|s| s.status == status).count();
+    match (count(BuildStatus::Failed), count(BuildStatus::Skipped)) {
+        (0, _) => Ok(()),
+        (failed, 0) => Err(anyhow!("{failed} of {} builds failed", summaries.len())),
+        (failed, skipped) => Err(anyhow!(
+            "{failed} of {} builds failed, {skipped} skipped",
+            summaries.len()
+        )),
+    }
+}
+
+/// Builds the project for a single target, unless `--skip-if-unchanged` finds that nothing changed
+/// since the last build.
+fn build_target(
+    arguments: &BuildArguments,
+    setup: &ProjectSetup,
+    target: &BuildSelection,
+) -> anyhow::Result<BuildStatus> {
+    if arguments.dry_run {
+        run_build(arguments, setup, target)?;
+        return Ok(BuildStatus::Succeeded);
+    }
+
+    let output_path = arguments.output_path(&setup.project, target)?;
+    let fingerprint = if arguments.skip_if_unchanged {
+        let fingerprint = BuildFingerprint::new(
+            &setup.project,
+            setup.unity_version,
+            &arguments.fingerprint_args(target, &output_path),
+        )?;
+
+        if output_path.exists()
+            && BuildFingerprint::read(&output_path).as_ref() == Some(&fingerprint)
+        {
+            MessageType::print_line(
+                "Unchanged",
+                format!(
+                    "Unity {} {} project in {}, skipping the build",
+                    setup.unity_version,
+                    target,
+                    setup.project.normalized_display()
+                ),
+                MessageType::Ok,
+            );
+            return Ok(BuildStatus::Unchanged);
+        }
+
+        Some(fingerprint)
+    } else {
+        None
+    };
+
+    BuildFingerprint::remove(&output_path);
+    run_build(arguments, setup, target)?;
+
+    if let Some(fingerprint) = fingerprint
+        && let Err(e) = fingerprint.write(&output_path)
+    {
+        MessageType::print_line("Warning", format!("{e:#}"), MessageType::Warning);
+    }
+
+    Ok(BuildStatus::Succeeded)
+}
+
+/// Builds the project for a single target in the open editor or in batch mode.
+fn run_build(
+    arguments: &BuildArguments,
+    setup: &ProjectSetup,
+    target: &BuildSelection,
+) -> anyhow::Result<()> {
+    let start_time = Utc::now();
 
     // Try to build via editor IPC if editor is running
-    if let Some(result) = try_editor_build(arguments, &setup)? {
-        return handle_editor_build_result(result, &setup, start_time);
+    if !arguments.dry_run
+        && let Some(result) = try_editor_build(arguments, target, setup)?
+    {
+        return handle_editor_build_result(result, arguments, setup, target, start_time);
     }
 
     // Fall back to batch mode build
     let editor_path = setup.editor_executable()?;
 
-    let output_path = arguments.output_path(&setup.project)?;
-    let log_path = arguments.full_log_path(&setup.project)?;
+    let output_path = arguments.output_path(&setup.project, target)?;
+    let log_path = arguments.full_log_path(&setup.project, target)?;
 
-    let build_command = arguments.create_cmd(&setup.project, &editor_path, &output_path, &log_path);
+    let build_command = arguments.create_cmd(
+        &setup.project,
+        target,
+        &editor_path,
+        &output_path,
+        &log_path,
+    );
 
     if arguments.dry_run {
         println!("{}", build_command_line(&build_command));
@@ -51,7 +185,7 @@ pub fn build_project(arguments: &BuildArguments) -> anyhow::Result<()> {
     let build_text = format!(
         "Unity {} {} project in {}",
         setup.unity_version,
-        arguments.target,
+        target,
         setup.project.normalized_display()
     );
 
@@ -62,19 +196,42 @@ pub fn build_project(arguments: &BuildArguments) -> anyhow::Result<()> {
         StatusLine::new_silent()
     };
 
+    warn_outdated_scripts(&setup.project);
     let hooks = csharp_build_script_injection_hooks(&setup.project, arguments.inject);
 
     (hooks.inject_build_script)()?;
 
-    if log_path.exists() {
-        fs::remove_file(&log_path)?;
-    }
-
-    let build_result = if arguments.show_log() {
-        wait_with_log_output(build_command, &log_path)
-    } else {
-        wait_with_stdout(build_command)
+    let command_line = build_command_line(&build_command);
+    let timeouts = Timeouts {
+        total: arguments.timeout,
+        idle: arguments.idle_timeout,
+        log_file: Some(log_path.clone()),
     };
+    let build_result = run_with_retries(
+        arguments.retries,
+        &log_path,
+        || {
+            if log_path.exists() {
+                fs::remove_file(&log_path)?;
+            }
+
+            // A command can only be spawned once, every attempt needs a new one.
+            let build_command = arguments.create_cmd(
+                &setup.project,
+                target,
+                &editor_path,
+                &output_path,
+                &log_path,
+            );
+
+            if arguments.show_log() {
+                wait_with_log_output(build_command, &log_path, &timeouts)
+            } else {
+                wait_with_stdout(build_command, &timeouts)
+            }
+        },
+        Result::is_err,
+    );
 
     (hooks.cleanup_build_script)()?;
     drop(build_status);
@@ -93,7 +250,7 @@ pub fn build_project(arguments: &BuildArguments) -> anyhow::Result<()> {
         format!(
             "building Unity {} {} project in {}",
             setup.unity_version,
-            arguments.target,
+            target,
             setup.project.normalized_display()
         ),
         build_status,
@@ -109,11 +266,168 @@ pub fn build_project(arguments: &BuildArguments) -> anyhow::Result<()> {
     );
 
     print_build_report(&log_path, build_status);
+
+    if build_result.is_ok() {
+        package_output(arguments, setup, target, command_line, &output_path)?;
+    }
+
     check_version_issues(setup.unity_version);
-    build_result.map_err(|_| collect_log_errors(&log_path))
+    build_result.map_err(|e| {
+        if e.timed_out {
+            e.into()
+        } else {
+            report_log_errors(&log_path, &setup.project)
+        }
+    })
+}
+
+/// Lists the configured build presets with the command line each of them resolves to.
+pub fn list_build_presets(arguments: &BuildArguments, matches: &ArgMatches) -> anyhow::Result<()> {
+    let setup = ProjectSetup::new(arguments.config_dir())?;
+    let config = Config::load(arguments.config_dir())?;
+    let presets = config.presets();
+
+    if presets.is_empty() {
+        println!(
+            "No presets configured, add `[presets.<name>]` sections to `{}`",
+            setup.project.join(PROJECT_CONFIG_FILE).normalized_display()
+        );
+        return Ok(());
+    }
+
+    let editor_path = setup.editor_executable()?;
+    for preset in presets {
+        let mut preset_arguments = BuildArguments::from_arg_matches(matches)?;
+        preset_arguments.preset = Some(preset.name.to_string());
+        apply_loaded_config(&mut preset_arguments, matches, &config)?;
+
+        println!(
+            "{} ({})",
+            preset.name.bold(),
+            preset.file.path.normalized_display()
+        );
+
+        let command_lines = preset_arguments
+            .build_selections(&setup)
+            .and_then(|selections| {
+                selections
+                    .iter()
+                    .map(|target| {
+                        let output_path = preset_arguments.output_path(&setup.project, target)?;
+                        let log_path = preset_arguments.full_log_path(&setup.project, target)?;
+                        let cmd = preset_arguments.create_cmd(
+                            &setup.project,
+                            target,
+                            &editor_path,
+                            &output_path,
+                            &log_path,
+                        );
+                        Ok(build_command_line(&cmd))
+                    })
+                    .collect::<anyhow::Result<Vec<_>>>()
+            });
+
+        match command_lines {
+            Ok(command_lines) => {
+                for command_line in command_lines {
+                    println!("{INDENT}{command_line}");
+                }
+            }
+            Err(e) => println!("{INDENT}{}", e.to_string().paint(ERROR)),
+        }
+    }
+    Ok(())
+}
+
+/// What to build: a target platform, or a Unity 6 build profile that determines the platform.
+enum BuildSelection {
+    Platform(BuildOpenTarget),
+    /// Path to the build profile asset, relative to the project directory.
+    Profile(PathBuf),
+}
+
+impl BuildSelection {
+    /// Returns the name used for the default output directory and log file.
+    fn name(&self) -> String {
+        match self {
+            Self::Platform(target) => target.as_ref().to_string(),
+            Self::Profile(path) => path
+                .file_stem()
+                .unwrap_or_default()
+                .to_string_lossy()
+                .to_string(),
+        }
+    }
+}
+
+impl Display for BuildSelection {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Platform(target) => write!(f, "{target}"),
+            Self::Profile(_) => write!(f, "`{}` profile", self.name()),
+        }
+    }
 }
 
 impl BuildArguments {
+    /// Returns what to build. The build profile takes precedence over the targets, which can also
+    /// be set by the environment or a configuration file.
+    fn build_selections(&self, setup: &ProjectSetup) -> anyhow::Result<Vec<BuildSelection>> {
+        if let Some(symbol) = self
+            .defines
+            .iter()
+            .find(|d| d.is_empty() || d.contains(|c: char| c == ';' || c.is_whitespace()))
+        {
+            return Err(anyhow!("Invalid scripting define symbol: `{symbol}`"));
+        }
+
+        if let Some(profile) = &self.profile {
+            if !self.defines.is_empty() {
+                return Err(anyhow!(
+                    "Scripting define symbols cannot be added to a build profile, set them in the profile instead"
+                ));
+            }
+
+            let profile = setup
+                .project
+                .find_build_profile(setup.unity_version, profile)?;
+            return Ok(vec![BuildSelection::Profile(profile)]);
+        }
+
+        if self.all_targets && self.all_targets_list.is_empty() {
+            return Err(anyhow!(
+                "No targets to build with --all-targets. Set `all_targets` in the `[build]` section of the configuration."
+            ));
+        }
+
+        if self.selected_targets().is_empty() {
+            return Err(anyhow!(
+                "No build target specified. Pass it as an argument, set `{ENV_BUILD_TARGET}` or set `target` in the `[build]` section of the configuration or in a preset."
+            ));
+        }
+
+        Ok(self
+            .selected_targets()
+            .iter()
+            .unique()
+            .map(|target| BuildSelection::Platform(*target))
+            .collect())
+    }
+
+    /// Returns the targets to build, ignoring a build profile.
+    fn selected_targets(&self) -> &[BuildOpenTarget] {
+        if self.all_targets {
+            &self.all_targets_list
+        } else {
+            &self.targets
+        }
+    }
+
+    /// Returns true if multiple targets are built, which then need their own output and log paths.
+    fn builds_multiple_targets(&self) -> bool {
+        self.profile.is_none() && self.selected_targets().iter().unique().count() > 1
+    }
+
     /// Returns true if the log should be shown.
     fn show_log(&self) -> bool {
         !self.quiet && (self.mode == BuildMode::Batch || self.mode == BuildMode::BatchNoGraphics)
@@ -121,11 +435,24 @@ impl BuildArguments {
 
     /// Returns the full path to the log file.
     /// By default, the project's `Logs` directory is used as destination.
-    fn full_log_path(&self, project: &ProjectPath) -> anyhow::Result<PathBuf> {
-        let log_file = self.log_file.as_deref().map_or_else(
-            || format!("Build-{}.log", self.target).into(),
-            std::borrow::ToOwned::to_owned,
-        );
+    fn full_log_path(
+        &self,
+        project: &ProjectPath,
+        target: &BuildSelection,
+    ) -> anyhow::Result<PathBuf> {
+        let log_file = match self.log_file.as_deref() {
+            None => format!("Build-{}.log", target.name()).into(),
+            Some(path) if self.builds_multiple_targets() => {
+                // Give each target its own log file.
+                let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+                let file_name = match path.extension() {
+                    Some(ext) => format!("{stem}-{}.{}", target.name(), ext.to_string_lossy()),
+                    None => format!("{stem}-{}", target.name()),
+                };
+                path.with_file_name(file_name)
+            }
+            Some(path) => path.to_path_buf(),
+        };
 
         let file_name = log_file
             .file_name()
@@ -144,15 +471,21 @@ impl BuildArguments {
     }
 
     /// Returns the output path for the build.
-    fn output_path(&self, project: &ProjectPath) -> anyhow::Result<PathBuf> {
+    fn output_path(
+        &self,
+        project: &ProjectPath,
+        target: &BuildSelection,
+    ) -> anyhow::Result<PathBuf> {
         let output_dir = match &self.build_path {
+            // Give each target its own subdirectory.
+            Some(path) if self.builds_multiple_targets() => path.absolutize()?.join(target.name()),
             Some(path) => path.absolutize()?.into(),
             None => {
                 // If no build path is given, use <project>/Builds/<target>
                 project
                     .join("Builds")
                     .join(self.output_type.as_ref())
-                    .join(self.target.as_ref())
+                    .join(target.name())
             }
         };
 
@@ -169,32 +502,27 @@ impl BuildArguments {
     fn create_cmd(
         &self,
         project: &ProjectPath,
+        target: &BuildSelection,
         editor_exe: &Path,
         output_dir: &Path,
         log_file: &Path,
     ) -> Command {
         // Build the command using the builder pattern.
         let mut builder = UnityCommandBuilder::new(editor_exe.to_path_buf())
-            .with_project_path(project.to_path_buf())
-            .with_build_target(self.target.as_ref())
+            .with_project_path(project.to_path_buf());
+
+        builder = match target {
+            BuildSelection::Platform(target) => builder.with_build_target(target.as_ref()),
+            BuildSelection::Profile(profile) => builder
+                .add_arg("-activeBuildProfile")
+                .add_arg(profile.normalized_display().to_string()),
+        };
+
+        builder = builder
             .with_log_file(log_file)
             .add_arg("-executeMethod")
             .add_arg(&self.build_function)
-            .add_arg("--ucom-build-output")
-            .add_arg(output_dir.to_string_lossy().to_string())
-            .add_arg("--ucom-build-target")
-            .add_arg(BuildScriptTarget::from(self.target).as_ref());
-
-        let build_options = self.build_option_flags();
-        if build_options != (BuildOptions::None as i32) {
-            builder = builder
-                .add_arg("--ucom-build-options")
-                .add_arg(build_options.to_string());
-        }
-
-        if let Some(build_args) = &self.build_args {
-            builder = builder.add_arg("--ucom-pre-build-args").add_arg(build_args);
-        }
+            .add_args(self.build_script_args(target, output_dir));
 
         // Add the build mode flags.
         match self.mode {
@@ -218,6 +546,62 @@ impl BuildArguments {
         builder.build()
     }
 
+    /// Returns the arguments that determine the output of a build, for its fingerprint. The
+    /// post-build steps are included, a build with another archive or cleaning isn't skipped.
+    fn fingerprint_args(&self, target: &BuildSelection, output_dir: &Path) -> Vec<String> {
+        let mut args = vec!["-executeMethod".to_string(), self.build_function.clone()];
+        args.extend(self.build_script_args(target, output_dir));
+        args.extend(self.args.iter().flatten().cloned());
+
+        if let Some(format) = self.archive {
+            args.extend(["--archive".to_string(), format.to_string()]);
+        }
+        if self.symbols_archive {
+            args.push("--symbols-archive".to_string());
+        }
+        if self.clean {
+            args.push("--clean".to_string());
+        }
+        args
+    }
+
+    /// Returns the arguments that the build script reads from the command line.
+    fn build_script_args(&self, target: &BuildSelection, output_dir: &Path) -> Vec<String> {
+        let mut args = vec![
+            "--ucom-build-output".to_string(),
+            output_dir.to_string_lossy().to_string(),
+        ];
+
+        match target {
+            BuildSelection::Platform(target) => args.extend([
+                "--ucom-build-target".to_string(),
+                BuildScriptTarget::from(*target).as_ref().to_string(),
+            ]),
+            BuildSelection::Profile(profile) => args.extend([
+                "--ucom-build-profile".to_string(),
+                profile.normalized_display().to_string(),
+            ]),
+        }
+
+        let build_options = self.build_option_flags();
+        if build_options != (BuildOptions::None as i32) {
+            args.extend([
+                "--ucom-build-options".to_string(),
+                build_options.to_string(),
+            ]);
+        }
+
+        if let Some(build_args) = &self.build_args {
+            args.extend(["--ucom-pre-build-args".to_string(), build_args.clone()]);
+        }
+
+        if !self.defines.is_empty() {
+            args.extend(["--ucom-add-defines".to_string(), self.defines.join(";")]);
+        }
+
+        args
+    }
+
     fn build_option_flags(&self) -> i32 {
         let mut option_flags = 0;
         if self.run_player {
@@ -276,16 +660,80 @@ fn print_build_report(log_path: &Path, status: MessageType) {
     }
 }
 
+/// Writes the build manifest to the output directory and archives the output if requested.
+fn package_output(
+    arguments: &BuildArguments,
+    setup: &ProjectSetup,
+    target: &BuildSelection,
+    command_line: String,
+    output_path: &Path,
+) -> Result<()> {
+    write_build_manifest(arguments, setup, target, command_line, output_path);
+
+    if let Some(format) = arguments.archive {
+        archive_output(
+            &setup.project,
+            output_path,
+            &target.name(),
+            format,
+            arguments.symbols_archive,
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Writes the build manifest next to the output directory. Failing to do so does not fail the build.
+fn write_build_manifest(
+    arguments: &BuildArguments,
+    setup: &ProjectSetup,
+    target: &BuildSelection,
+    command_line: String,
+    output_path: &Path,
+) {
+    let profile = match target {
+        BuildSelection::Platform(_) => None,
+        BuildSelection::Profile(path) => Some(path.as_path()),
+    };
+
+    let manifest = BuildManifest::new(
+        &setup.project,
+        setup.unity_version,
+        &target.name(),
+        profile,
+        command_line,
+        arguments.build_option_flags(),
+    )
+    .and_then(|manifest| manifest.write(output_path));
+
+    match manifest {
+        Ok(path) => MessageType::print_line(
+            "Manifest",
+            path.normalized_display().to_string(),
+            MessageType::Ok,
+        ),
+        Err(e) => MessageType::print_line(
+            "Warning",
+            format!("Cannot write the build manifest: {e:#}"),
+            MessageType::Warning,
+        ),
+    }
+}
+
+/// Returns true if the path is a directory with Burst debug information or the IL2CPP backup,
+/// which Unity writes to the output directory but should not be shipped.
+pub fn is_do_not_ship_dir(path: &Path) -> bool {
+    let dir_str = path.to_string_lossy();
+    path.is_dir()
+        && (dir_str.ends_with("_BurstDebugInformation_DoNotShip")
+            || dir_str.ends_with("_BackUpThisFolder_ButDontShipItWithYourGame"))
+}
+
 fn clean_output_directory(path: &Path) -> anyhow::Result<()> {
     let to_delete = fs::read_dir(path)?
         .flatten()
         .map(|de| de.path())
-        .filter(|p| p.is_dir()) // Only directories.
-        .filter(|p| {
-            let dir_str = p.to_string_lossy();
-            dir_str.ends_with("_BurstDebugInformation_DoNotShip")
-                || dir_str.ends_with("_BackUpThisFolder_ButDontShipItWithYourGame")
-        });
+        .filter(|p| is_do_not_ship_dir(p));
 
     for dir in to_delete {
         println!("Removing directory: {}", dir.normalized_display());
@@ -296,49 +744,6 @@ fn clean_output_directory(path: &Path) -> anyhow::Result<()> {
     Ok(())
 }
 
-/// Returns errors from the given log file as one collected Err.
-fn collect_log_errors(log_file: &Path) -> anyhow::Error {
-    let Ok(log_file) = File::open(log_file) else {
-        return anyhow!("Failed to open log file: {}", log_file.normalized_display());
-    };
-
-    let errors = BufReader::new(log_file)
-        .lines()
-        .map_while(Result::ok)
-        .filter(|l| line_contains_error(l))
-        .unique()
-        .collect_vec();
-
-    match &errors[..] {
-        [] => anyhow!("No errors found in log"),
-        [single_error] => anyhow!("{single_error}"),
-        _ => {
-            let joined = errors
-                .iter()
-                .enumerate()
-                .map(|(i, error)| format!("{c}: {e}", c = i + 1, e = error))
-                .join("\n");
-
-            anyhow!(joined)
-        }
-    }
-}
-
-/// Returns true if the given line is an error.
-fn line_contains_error(line: &str) -> bool {
-    const ERROR_PREFIXES: &[&str] = &[
-        "[Builder] Error:",
-        "error CS",
-        "Fatal Error",
-        "Error building Player",
-        "error:",
-        "BuildFailedException:",
-        "System.Exception:",
-    ];
-
-    ERROR_PREFIXES.iter().any(|prefix| line.contains(prefix))
-}
-
 /// Represents a hook function that returns a result.
 type HookFn = Box<dyn FnOnce() -> anyhow::Result<()>>;
 
@@ -389,6 +794,14 @@ fn csharp_build_script_injection_hooks(project: &ProjectPath, inject: InjectActi
             let closure_script_dir = PathBuf::from(&unique_dir_name).join("Editor");
             let closure_remove_dir = project.join(&unique_dir_name);
 
+            // Also remove the script when ucom is interrupted, otherwise it is left in the project.
+            let interrupt_remove_dir = closure_remove_dir.clone();
+            let interrupt_guard = CleanupGuard::new(move || {
+                if let Err(e) = cleanup_csharp_build_script(interrupt_remove_dir) {
+                    eprintln!("{e:#}");
+                }
+            });
+
             BuildHooks::new(
                 Box::new(|| {
                     add_file_to_project(
@@ -397,7 +810,10 @@ fn csharp_build_script_injection_hooks(project: &ProjectPath, inject: InjectActi
                         UnityTemplateFile::Builder,
                     )
                 }),
-                Box::new(|| cleanup_csharp_build_script(closure_remove_dir)),
+                Box::new(|| {
+                    drop(interrupt_guard);
+                    cleanup_csharp_build_script(closure_remove_dir)
+                }),
             )
         }
 
@@ -424,19 +840,18 @@ fn csharp_build_script_injection_hooks(project: &ProjectPath, inject: InjectActi
 }
 
 /// Removes the injected build script from the project.
-fn cleanup_csharp_build_script(parent_dir: impl AsRef<Path>) -> anyhow::Result<()> {
+pub fn cleanup_csharp_build_script(parent_dir: impl AsRef<Path>) -> anyhow::Result<()> {
     let parent_dir = parent_dir.as_ref();
-    if !parent_dir.exists() {
-        return Ok(());
-    }
 
     // Remove the directory where the build script is located.
-    fs::remove_dir_all(parent_dir).with_context(|| {
-        format!(
-            "Could not remove temporary directory: {}",
-            parent_dir.normalized_display()
-        )
-    })?;
+    if parent_dir.exists() {
+        fs::remove_dir_all(parent_dir).with_context(|| {
+            format!(
+                "Could not remove temporary directory: {}",
+                parent_dir.normalized_display()
+            )
+        })?;
+    }
 
     // Remove the .meta file.
     let meta_file = parent_dir.with_extension("meta");
@@ -455,98 +870,113 @@ fn cleanup_csharp_build_script(parent_dir: impl AsRef<Path>) -> anyhow::Result<(
 /// Returns `Err` if communication fails or the build fails.
 fn try_editor_build(
     args: &BuildArguments,
+    target: &BuildSelection,
     setup: &ProjectSetup,
 ) -> Result<Option<EditorBuildResult>> {
-    if !is_unity_editor_running(&setup.project)? {
+    let Some(channel) = EditorChannel::open(&setup.project)? else {
         return Ok(None);
-    }
-
-    // Check if UnityBuilder.cs is installed (contains the editor watcher)
-    let builder_file_name = UnityTemplateFile::Builder.as_asset().filename;
-    let builder_script_path = setup
-        .project
-        .join(PERSISTENT_BUILD_SCRIPT_ROOT)
-        .join(builder_file_name);
-
-    if !builder_script_path.exists() {
-        return Err(anyhow!(
-            "Unity editor is running, but {} not installed.\n\n\
-             To enable building via the running editor, install the builder script:\n\
-             \n\
-             {}\n\
-             \n\
-             This will enable both batch mode and editor IPC builds.\n\
-             Or close the Unity editor to build in batch mode.",
-            builder_file_name,
-            format_args!("  ucom add builder {}", setup.project.normalized_display())
-        ));
-    }
+    };
 
     MessageType::print_line(
         "Building via editor",
         format!(
             "Unity {} {} project in {}",
             setup.unity_version,
-            args.target,
+            target,
             setup.project.normalized_display()
         ),
         MessageType::Info,
     );
 
-    let uuid = Uuid::new_v4();
-    let temp_dir = setup.project.join("Temp");
-    let command_dir = temp_dir.join("ucom-commands");
-    let result_dir = temp_dir.join("ucom-results");
-
-    fs::create_dir_all(&command_dir)?;
-    fs::create_dir_all(&result_dir)?;
+    let output_path = args.output_path(&setup.project, target)?;
+    let log_path = args.full_log_path(&setup.project, target)?;
 
-    let output_path = args.output_path(&setup.project)?;
-    let log_path = args.full_log_path(&setup.project)?;
+    let (platform, build_profile) = match target {
+        BuildSelection::Platform(target) => (
+            BuildScriptTarget::from(*target).as_ref().to_string(),
+            String::new(),
+        ),
+        BuildSelection::Profile(profile) => {
+            (String::new(), profile.normalized_display().to_string())
+        }
+    };
 
-    let command = EditorCommand {
-        command: "build".to_string(),
-        uuid: uuid.to_string(),
-        timestamp: Utc::now().to_rfc3339(),
-        platform: BuildScriptTarget::from(args.target).as_ref().to_string(),
+    let command = EditorCommand::Build(BuildCommand {
+        platform,
+        build_profile,
         output_path: output_path.to_string_lossy().to_string(),
         log_path: log_path.to_string_lossy().to_string(),
         build_options: args.build_option_flags(),
+        defines: args.defines.clone(),
+        build_args: args.build_args.clone().unwrap_or_default(),
+        build_function: args.build_function.clone(),
+        args: editor_build_args(args, target, &output_path, &log_path),
         development_build: args.development_build,
         force_platform_switch: args.force_editor_build,
         force_play_mode_exit: args.force_editor_build,
-    };
-
-    let command_file = command_dir.join(format!("build-{uuid}.json"));
-    let command_json = serde_json::to_string_pretty(&command)?;
-    fs::write(&command_file, command_json)?;
-
-    let result = poll_for_result(&result_dir, &uuid)?;
-    // NOTE: When the user cancels the polling, files are left behind. Which is fine.
+    });
 
-    let _ = fs::remove_file(&command_file);
-    let result_file = result_dir.join(format!("build-{uuid}.json"));
-    let _ = fs::remove_file(&result_file);
+    let status = StatusLine::new("Building", "waiting for the editor");
+    let mut progress = BuildProgress::default();
+    let result = channel
+        .send(&command)?
+        .wait_for_result_with_progress(args.timeout, |event| {
+            progress.update(event);
+            status.update_line("Building", progress.to_string());
+        })?;
 
     Ok(Some(result))
 }
 
-/// Polls for a result file from the Unity editor.
-///
-/// Returns the parsed result when the editor completes the build.
-/// Polls indefinitely - user can press Ctrl+C to cancel.
-fn poll_for_result(result_dir: &Path, uuid: &Uuid) -> Result<EditorBuildResult> {
-    let result_file = result_dir.join(format!("build-{uuid}.json"));
-    let poll_interval = Duration::from_millis(500);
+/// Returns the arguments of a batch mode build without the ones that start Unity, for the build
+/// function in the editor.
+fn editor_build_args(
+    args: &BuildArguments,
+    target: &BuildSelection,
+    output_path: &Path,
+    log_path: &Path,
+) -> Vec<String> {
+    let mut editor_args = vec![
+        "-logFile".to_string(),
+        log_path.to_string_lossy().to_string(),
+    ];
+    editor_args.extend(args.build_script_args(target, output_path));
+    editor_args.extend(args.args.iter().flatten().cloned());
+    editor_args
+}
 
-    loop {
-        if result_file.exists() {
-            let json = fs::read_to_string(&result_file)?;
-            let result: EditorBuildResult = serde_json::from_str(&json)?;
-            return Ok(result);
+/// The progress of an editor build, as shown in the status line.
+#[derive(Default)]
+struct BuildProgress {
+    phase: String,
+    percentage: Option<f32>,
+    errors: usize,
+    warnings: usize,
+}
+
+impl BuildProgress {
+    fn update(&mut self, event: ProgressEvent) {
+        match event {
+            ProgressEvent::Phase { phase, percentage } => {
+                self.phase = phase;
+                self.percentage = (percentage >= 0.0).then_some(percentage);
+            }
+            ProgressEvent::Message(message) if message.is_error() => self.errors += 1,
+            ProgressEvent::Message(_) => self.warnings += 1,
         }
+    }
+}
 
-        std::thread::sleep(poll_interval);
+impl Display for BuildProgress {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.phase)?;
+        if let Some(percentage) = self.percentage {
+            write!(f, " {percentage:.0}%")?;
+        }
+        match (self.errors, self.warnings) {
+            (0, 0) => Ok(()),
+            (errors, warnings) => write!(f, " ({errors} errors, {warnings} warnings)"),
+        }
     }
 }
 
@@ -555,9 +985,13 @@ fn poll_for_result(result_dir: &Path, uuid: &Uuid) -> Result<EditorBuildResult>
 /// Returns `Ok(())` if the build succeeded, `Err` otherwise.
 fn handle_editor_build_result(
     result: EditorBuildResult,
+    arguments: &BuildArguments,
     setup: &ProjectSetup,
+    target: &BuildSelection,
     start_time: chrono::DateTime<Utc>,
 ) -> Result<()> {
+    let output_path = arguments.output_path(&setup.project, target)?;
+
     match result.status.as_str() {
         "error" => return Err(anyhow!("{}", result.message)),
         "failed" => {
@@ -597,6 +1031,10 @@ fn handle_editor_build_result(
                 );
             }
 
+            if arguments.clean {
+                clean_output_directory(&output_path)?;
+            }
+
             MessageType::print_line(
                 "Succeeded",
                 format!(
@@ -631,11 +1069,18 @@ fn handle_editor_build_result(
         },
     );
 
-    if result.status == "success" {
-        Ok(())
+    let build_result = if result.status == "success" {
+        // The build ran in the open editor, so the manifest records the command line of ucom.
+        let command_line = std::env::args().join(" ");
+        package_output(arguments, setup, target, command_line, &output_path)
     } else {
-        Err(anyhow!("Build failed"))
-    }
+        let diagnostics: Vec<Diagnostic> = result.messages.iter().map(Diagnostic::from).collect();
+        Err(report_diagnostics(&diagnostics, &setup.project)
+            .unwrap_or_else(|| anyhow!("Build failed")))
+    };
+
+    check_version_issues(setup.unity_version);
+    build_result
 }
 
 /// Prints the build report from editor build result.
@@ -669,19 +1114,78 @@ fn print_editor_build_report(result: &EditorBuildResult, status: MessageType) {
     );
 }
 
-/// Command structure sent to Unity editor via JSON file.
-#[derive(Serialize, Debug)]
-struct EditorCommand {
-    command: String,
-    uuid: String,
-    timestamp: String,
-    platform: String,
-    output_path: String,
-    log_path: String,
-    build_options: i32,
-    development_build: bool,
-    force_platform_switch: bool,
-    force_play_mode_exit: bool,
+//
+// Build summary
+//
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum BuildStatus {
+    Succeeded,
+    /// Not built, because nothing changed since the last build.
+    Unchanged,
+    Failed,
+    /// Not built, because an earlier build failed.
+    Skipped,
+}
+
+/// The outcome of building one of multiple targets.
+struct BuildSummary {
+    target: String,
+    status: BuildStatus,
+    /// Duration in seconds.
+    duration: Option<f64>,
+    output_size: Option<u64>,
+}
+
+impl BuildSummary {
+    fn skipped(target: &BuildSelection) -> Self {
+        Self {
+            target: target.to_string(),
+            status: BuildStatus::Skipped,
+            duration: None,
+            output_size: None,
+        }
+    }
+}
+
+/// Prints a table with the status, duration and output size of each build.
+fn print_build_summary(summaries: &[BuildSummary]) {
+    let target_width = summaries
+        .iter()
+        .map(|s| s.target.len())
+        .max()
+        .unwrap_or_default()
+        .max("Target".len());
+
+    println!();
+    println!(
+        "{}",
+        format!(
+            "{:<target_width$}  {:<9}  {:>10}  {:>10}",
+            "Target", "Status", "Duration", "Size"
+        )
+        .bold()
+    );
+
+    for summary in summaries {
+        let status = format!("{:<9}", format!("{:?}", summary.status));
+        let status = match summary.status {
+            BuildStatus::Succeeded | BuildStatus::Unchanged => status.paint(OK),
+            BuildStatus::Failed => status.paint(ERROR),
+            BuildStatus::Skipped => status.paint(UNSTYLED).dim(),
+        };
+
+        println!(
+            "{:<target_width$}  {status}  {:>10}  {:>10}",
+            summary.target,
+            summary
+                .duration
+                .map_or_else(|| "-".to_string(), |d| format!("{d:.2}s")),
+            summary
+                .output_size
+                .map_or_else(|| "-".to_string(), format_size),
+        );
+    }
 }
 
 /// Result structure received from Unity editor via JSON file.
@@ -703,4 +1207,7 @@ struct EditorBuildResult {
     total_size: Option<u64>,
     total_errors: Option<i32>,
     total_warnings: Option<i32>,
+    /// The compiler messages and build errors, empty for builder scripts before version 3.
+    #[serde(default)]
+    messages: Vec<EditorMessage>,
 }
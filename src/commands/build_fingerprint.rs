@@ -0,0 +1,235 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+use itertools::Itertools;
+use sha2::{Digest, Sha256};
+use walkdir::WalkDir;
+
+use crate::unity::project::Manifest;
+use crate::unity::{ProjectPath, Version};
+use crate::utils::download::file_digest;
+use crate::utils::path_ext::PlatformConsistentPathExt;
+
+/// The extension of the file next to the output directory that holds the fingerprint.
+const FINGERPRINT_EXTENSION: &str = "ucom-fingerprint";
+
+/// The directories of the project that a build depends on. `Packages` holds the manifest, the
+/// lock file and the embedded packages.
+const PROJECT_INPUTS: [&str; 3] = ["Assets", "Packages", "ProjectSettings"];
+
+/// The prefix of a local package dependency in `Packages/manifest.json`.
+const LOCAL_PACKAGE_PREFIX: &str = "file:";
+
+/// A hash of everything that determines the output of a build: the content of the project files
+/// and local packages, the editor version and the build arguments.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BuildFingerprint(String);
+
+impl BuildFingerprint {
+    /// Computes the fingerprint of building the project with the arguments.
+    pub fn new(
+        project: &ProjectPath,
+        unity_version: Version,
+        build_args: &[String],
+    ) -> anyhow::Result<Self> {
+        let mut hasher = Sha256::new();
+        hash_field(&mut hasher, "editor", &unity_version.to_string());
+        for arg in build_args {
+            hash_field(&mut hasher, "arg", arg);
+        }
+
+        for input in PROJECT_INPUTS {
+            hash_files(&mut hasher, input, &project.join(input))?;
+        }
+
+        // Local packages outside the project, the manifest was hashed with the `Packages` directory.
+        let packages_dir = project.join("Packages");
+        let local_packages = Manifest::from_project(project)
+            .map(|manifest| manifest.dependencies)
+            .unwrap_or_default();
+        for (name, source) in local_packages {
+            if let Some(path) = source.strip_prefix(LOCAL_PACKAGE_PREFIX) {
+                hash_files(&mut hasher, &name, &packages_dir.join(path))?;
+            }
+        }
+
+        Ok(Self(
+            hasher
+                .finalize()
+                .iter()
+                .map(|b| format!("{b:02x}"))
+                .collect(),
+        ))
+    }
+
+    /// Reads the fingerprint of the last successful build into the output directory, if there is one.
+    pub fn read(output_dir: &Path) -> Option<Self> {
+        fs::read_to_string(fingerprint_path(output_dir))
+            .ok()
+            .map(|content| Self(content.trim().to_string()))
+    }
+
+    /// Writes the fingerprint next to the output directory.
+    pub fn write(&self, output_dir: &Path) -> anyhow::Result<()> {
+        let path = fingerprint_path(output_dir);
+        fs::write(&path, &self.0)
+            .with_context(|| format!("Cannot write fingerprint `{}`", path.normalized_display()))
+    }
+
+    /// Removes the fingerprint of the previous build, the output no longer matches it once a new
+    /// build starts.
+    pub fn remove(output_dir: &Path) {
+        let _ = fs::remove_file(fingerprint_path(output_dir));
+    }
+}
+
+/// Returns the path of the fingerprint file, e.g. `Builds/Release/win64.ucom-fingerprint`. It is
+/// kept out of the output directory, so it doesn't end up in the manifest or archive.
+fn fingerprint_path(output_dir: &Path) -> PathBuf {
+    let mut file_name = output_dir.file_name().unwrap_or_default().to_os_string();
+    file_name.push(".");
+    file_name.push(FINGERPRINT_EXTENSION);
+    output_dir.with_file_name(file_name)
+}
+
+/// Hashes the names and content of the files in a directory, or of a single file. The names are
+/// relative to the directory and prefixed with `name`, so they don't depend on where it is.
+/// Symbolic links are followed.
+fn hash_files(hasher: &mut Sha256, name: &str, path: &Path) -> anyhow::Result<()> {
+    if !path.exists() {
+        return Ok(());
+    }
+
+    for entry in WalkDir::new(path).follow_links(true).sort_by_file_name() {
+        let entry = entry?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+
+        // Paths are hashed with `/` separators, so the fingerprint is the same on all platforms.
+        let relative_path = entry
+            .path()
+            .strip_prefix(path)?
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .join("/");
+        hash_field(hasher, "file", &format!("{name}/{relative_path}"));
+        hasher.update(file_digest::<Sha256>(entry.path())?);
+    }
+
+    Ok(())
+}
+
+/// Adds a tagged, length-prefixed value, so different inputs can't produce the same byte stream.
+fn hash_field(hasher: &mut Sha256, tag: &str, value: &str) {
+    hasher.update(tag.as_bytes());
+    hasher.update((value.len() as u64).to_le_bytes());
+    hasher.update(value.as_bytes());
+}
+
+//
+// Tests
+//
+
+#[cfg(test)]
+mod build_fingerprint_tests {
+    use std::str::FromStr;
+
+    use super::*;
+
+    #[test]
+    fn test_fingerprint_path() {
+        assert_eq!(
+            fingerprint_path(Path::new("Builds/Release/win64")),
+            Path::new("Builds/Release/win64.ucom-fingerprint")
+        );
+    }
+
+    #[test]
+    fn test_fingerprint_changes_with_inputs() {
+        let dir = std::env::temp_dir().join(format!("ucom-fingerprint-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(dir.join("Assets/Scripts")).unwrap();
+        fs::create_dir_all(dir.join("ProjectSettings")).unwrap();
+        fs::write(
+            dir.join("ProjectSettings/ProjectVersion.txt"),
+            "m_EditorVersion: 6000.0.1f1",
+        )
+        .unwrap();
+        fs::write(dir.join("Assets/Scripts/Player.cs"), "class Player {}").unwrap();
+        fs::write(dir.join("README.md"), "Docs").unwrap();
+        fs::create_dir_all(dir.join("Packages/com.company.tools")).unwrap();
+        fs::write(
+            dir.join("Packages/com.company.tools/Tool.cs"),
+            "class Tool {}",
+        )
+        .unwrap();
+        fs::create_dir_all(dir.join("LocalPackages/com.company.core")).unwrap();
+        fs::write(
+            dir.join("LocalPackages/com.company.core/Core.cs"),
+            "class Core {}",
+        )
+        .unwrap();
+        fs::write(
+            dir.join("Packages/manifest.json"),
+            r#"{ "dependencies": { "com.company.core": "file:../LocalPackages/com.company.core" } }"#,
+        )
+        .unwrap();
+
+        let project = ProjectPath::try_from(dir.as_path()).unwrap();
+        let version = Version::from_str("6000.0.1f1").unwrap();
+        let args = vec!["--ucom-build-target".to_string(), "Android".to_string()];
+        let fingerprint = || BuildFingerprint::new(&project, version, &args).unwrap();
+
+        let original = fingerprint();
+        fs::write(dir.join("README.md"), "Changed docs").unwrap();
+        assert_eq!(fingerprint(), original);
+
+        fs::write(
+            dir.join("Packages/com.company.tools/Tool.cs"),
+            "class Tool { }",
+        )
+        .unwrap();
+        let embedded_changed = fingerprint();
+        assert_ne!(embedded_changed, original);
+
+        fs::write(
+            dir.join("LocalPackages/com.company.core/Core.cs"),
+            "class Core { }",
+        )
+        .unwrap();
+        let local_changed = fingerprint();
+        assert_ne!(local_changed, embedded_changed);
+
+        fs::write(dir.join("Assets/Scripts/Player.cs"), "class Player { }").unwrap();
+        let changed = fingerprint();
+        assert_ne!(changed, local_changed);
+
+        #[cfg(unix)]
+        {
+            fs::create_dir_all(dir.join("Shared")).unwrap();
+            fs::write(dir.join("Shared/Art.txt"), "v1").unwrap();
+            std::os::unix::fs::symlink(dir.join("Shared"), dir.join("Assets/Shared")).unwrap();
+            let linked = fingerprint();
+            fs::write(dir.join("Shared/Art.txt"), "v2").unwrap();
+            assert_ne!(fingerprint(), linked);
+            fs::remove_file(dir.join("Assets/Shared")).unwrap();
+            assert_eq!(fingerprint(), changed);
+        }
+
+        let other_args = vec!["--ucom-build-target".to_string(), "iOS".to_string()];
+        assert_ne!(
+            BuildFingerprint::new(&project, version, &other_args).unwrap(),
+            changed
+        );
+
+        let output_dir = dir.join("Builds/android");
+        fs::create_dir_all(&output_dir).unwrap();
+        changed.write(&output_dir).unwrap();
+        assert_eq!(BuildFingerprint::read(&output_dir), Some(changed));
+        BuildFingerprint::remove(&output_dir);
+        assert_eq!(BuildFingerprint::read(&output_dir), None);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
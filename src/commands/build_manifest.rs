@@ -0,0 +1,204 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use anyhow::Context;
+use chrono::{DateTime, Utc};
+use clap::ValueEnum;
+use itertools::Itertools;
+use serde::Serialize;
+use sha2::Sha256;
+use walkdir::WalkDir;
+
+use crate::cli_build::BuildOptions;
+use crate::unity::release_api::load_cached_releases;
+use crate::unity::{ProjectPath, Version};
+use crate::utils::download::file_digest;
+use crate::utils::path_ext::PlatformConsistentPathExt;
+
+/// The extension of the manifest file that is written next to the output directory.
+const MANIFEST_EXTENSION: &str = "build-manifest.json";
+
+const PACKAGES_LOCK_FILE: &str = "Packages/packages-lock.json";
+
+/// Describes the files produced by a build and how they were built.
+#[derive(Serialize)]
+pub struct BuildManifest {
+    created: DateTime<Utc>,
+    target: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    profile: Option<String>,
+    editor: EditorInfo,
+    /// SHA-256 of `Packages/packages-lock.json`, if the project has one.
+    packages_lock_sha256: Option<String>,
+    git: Option<GitInfo>,
+    command_line: String,
+    build_options: Vec<String>,
+    files: Vec<ManifestFile>,
+}
+
+#[derive(Serialize)]
+struct EditorInfo {
+    version: Version,
+    /// Only available if the version is found in the cached release data.
+    short_revision: Option<String>,
+}
+
+#[derive(Serialize)]
+struct GitInfo {
+    commit: String,
+    /// `None` if the HEAD is detached.
+    branch: Option<String>,
+}
+
+#[derive(Serialize)]
+struct ManifestFile {
+    /// Path relative to the output directory, always with `/` separators.
+    path: String,
+    size: u64,
+    sha256: String,
+}
+
+impl BuildManifest {
+    /// Collects the provenance of a build. The produced files are added when it is written.
+    pub fn new(
+        project: &ProjectPath,
+        unity_version: Version,
+        target: &str,
+        profile: Option<&Path>,
+        command_line: String,
+        build_options: i32,
+    ) -> anyhow::Result<Self> {
+        let lock_file = project.join(PACKAGES_LOCK_FILE);
+        let packages_lock_sha256 = if lock_file.exists() {
+            Some(sha256_hex(&lock_file)?)
+        } else {
+            None
+        };
+
+        Ok(Self {
+            created: Utc::now(),
+            target: target.to_string(),
+            profile: profile.map(|p| p.to_string_lossy().replace('\\', "/")),
+            editor: EditorInfo {
+                version: unity_version,
+                // Only the cached release data is used, a build shouldn't wait on the network.
+                short_revision: load_cached_releases().ok().and_then(|releases| {
+                    releases
+                        .get_by_version(unity_version)
+                        .ok()
+                        .map(|r| r.short_revision.clone())
+                }),
+            },
+            packages_lock_sha256,
+            git: GitInfo::read(project),
+            command_line,
+            build_options: build_option_names(build_options),
+            files: Vec::new(),
+        })
+    }
+
+    /// Hashes all files in the output directory and writes the manifest next to it.
+    /// Returns the path of the written manifest.
+    pub fn write(mut self, output_dir: &Path) -> anyhow::Result<PathBuf> {
+        let manifest_path = manifest_path(output_dir);
+
+        for entry in WalkDir::new(output_dir).sort_by_file_name() {
+            let entry = entry?;
+            if !entry.file_type().is_file() {
+                continue;
+            }
+
+            let relative_path = entry.path().strip_prefix(output_dir)?;
+            self.files.push(ManifestFile {
+                path: relative_path
+                    .components()
+                    .map(|c| c.as_os_str().to_string_lossy())
+                    .join("/"),
+                size: entry.metadata()?.len(),
+                sha256: sha256_hex(entry.path())?,
+            });
+        }
+
+        let json = serde_json::to_string_pretty(&self)?;
+        fs::write(&manifest_path, json).with_context(|| {
+            format!(
+                "Cannot write build manifest `{}`",
+                manifest_path.normalized_display()
+            )
+        })?;
+        Ok(manifest_path)
+    }
+}
+
+/// Returns the path of the manifest file, e.g. `Builds/Release/win64.build-manifest.json`. Like
+/// the build fingerprint, it is kept out of the output directory, so it doesn't end up in the
+/// archive or ship with the build.
+fn manifest_path(output_dir: &Path) -> PathBuf {
+    let mut file_name = output_dir.file_name().unwrap_or_default().to_os_string();
+    file_name.push(".");
+    file_name.push(MANIFEST_EXTENSION);
+    output_dir.with_file_name(file_name)
+}
+
+impl GitInfo {
+    /// Returns the current commit and branch, or `None` if the project is not in a git repository.
+    fn read(project: &Path) -> Option<Self> {
+        let git = |args: &[&str]| {
+            Command::new("git")
+                .current_dir(project)
+                .args(args)
+                .output()
+                .ok()
+                .filter(|output| output.status.success())
+                .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        };
+
+        Some(Self {
+            commit: git(&["rev-parse", "HEAD"])?,
+            branch: git(&["symbolic-ref", "--short", "-q", "HEAD"]),
+        })
+    }
+}
+
+/// Returns the names of the `BuildOptions` flags that are set.
+fn build_option_names(flags: i32) -> Vec<String> {
+    BuildOptions::value_variants()
+        .iter()
+        .filter(|&&option| option != BuildOptions::None && flags & (option as i32) != 0)
+        .map(|option| format!("{option:?}"))
+        .collect()
+}
+
+fn sha256_hex(path: &Path) -> anyhow::Result<String> {
+    Ok(file_digest::<Sha256>(path)?
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect())
+}
+
+//
+// Tests
+//
+
+#[cfg(test)]
+mod build_manifest_tests {
+    use super::*;
+
+    #[test]
+    fn test_manifest_path() {
+        assert_eq!(
+            manifest_path(Path::new("Builds/Release/win64")),
+            Path::new("Builds/Release/win64.build-manifest.json")
+        );
+    }
+
+    #[test]
+    fn test_build_option_names() {
+        assert!(build_option_names(BuildOptions::None as i32).is_empty());
+        assert_eq!(
+            build_option_names(BuildOptions::Development as i32 | BuildOptions::StrictMode as i32),
+            vec!["Development", "StrictMode"]
+        );
+    }
+}
@@ -0,0 +1,186 @@
+use std::fs;
+
+use anyhow::anyhow;
+
+use crate::cli_add::UnityTemplateFile;
+use crate::commands::{INDENT, NO_TEST_FRAMEWORK_DEFINE, PERSISTENT_BUILD_SCRIPT_ROOT};
+use crate::unity::ProjectPath;
+use crate::utils::status_line::MessageType;
+
+/// The comment in a template file that holds its version.
+const TEMPLATE_VERSION_MARKER: &str = "// ucom-template-version:";
+
+/// How a script in the project compares to the template of this version of ucom.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum TemplateStatus {
+    /// The script is the same as the template.
+    UpToDate,
+    /// The script has the version of the template, but was changed.
+    Modified,
+    /// The script has another version than the template, `None` if it was added before the
+    /// templates were versioned.
+    VersionMismatch {
+        installed: Option<u32>,
+        expected: Option<u32>,
+    },
+}
+
+impl TemplateStatus {
+    /// Compares the content of a script with the content of its template.
+    pub fn new(installed: &str, template: &str) -> Self {
+        let installed = normalize(installed);
+        let template = normalize(template);
+
+        if installed == template {
+            return Self::UpToDate;
+        }
+
+        let installed_version = template_version(&installed);
+        let expected_version = template_version(&template);
+        if installed_version == expected_version {
+            Self::Modified
+        } else {
+            Self::VersionMismatch {
+                installed: installed_version,
+                expected: expected_version,
+            }
+        }
+    }
+
+    /// Returns the status of the script that was added to the project with `ucom add`, or `None` if
+    /// the project doesn't have it.
+    pub fn of_installed(
+        project: &ProjectPath,
+        template: UnityTemplateFile,
+    ) -> anyhow::Result<Option<Self>> {
+        let asset = template.as_asset();
+        let path = project
+            .join(PERSISTENT_BUILD_SCRIPT_ROOT)
+            .join(asset.filename);
+
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let installed = fs::read_to_string(&path)?;
+        Ok(Some(Self::new(&installed, &asset.load_content()?)))
+    }
+}
+
+/// Returns the version of a template, or `None` if it doesn't have one.
+pub fn template_version(content: &str) -> Option<u32> {
+    content
+        .lines()
+        .find_map(|line| line.trim().strip_prefix(TEMPLATE_VERSION_MARKER))
+        .and_then(|version| version.trim().parse().ok())
+}
+
+/// Returns true if the scripts are the same, ignoring line endings and the define that ucom adds.
+pub fn is_same_script(a: &str, b: &str) -> bool {
+    normalize(a) == normalize(b)
+}
+
+/// Returns an error if the builder script in the project has another version than the template,
+/// because the editor IPC protocol may differ. Warns about an outdated editor menu script.
+pub fn ensure_compatible_builder_script(project: &ProjectPath) -> anyhow::Result<()> {
+    if let Some(TemplateStatus::VersionMismatch {
+        installed,
+        expected,
+    }) = TemplateStatus::of_installed(project, UnityTemplateFile::Builder)?
+    {
+        return Err(anyhow!(
+            "{} in the project is {}, this version of ucom requires {}.\n\n\
+             The editor IPC protocol may differ, update the script with:\n\
+             \n\
+             {INDENT}ucom add builder --update",
+            UnityTemplateFile::Builder.as_asset().filename,
+            version_text(installed),
+            version_text(expected),
+        ));
+    }
+
+    warn_version_mismatch(project, UnityTemplateFile::BuilderMenu);
+    Ok(())
+}
+
+/// Prints a warning for each ucom script in the project that has another version than the template.
+pub fn warn_outdated_scripts(project: &ProjectPath) {
+    warn_version_mismatch(project, UnityTemplateFile::Builder);
+    warn_version_mismatch(project, UnityTemplateFile::BuilderMenu);
+}
+
+fn warn_version_mismatch(project: &ProjectPath, template: UnityTemplateFile) {
+    if let Ok(Some(TemplateStatus::VersionMismatch {
+        installed,
+        expected,
+    })) = TemplateStatus::of_installed(project, template)
+    {
+        MessageType::print_line(
+            "Warning",
+            format!(
+                "{} in the project is {}, this version of ucom expects {}; update it with `ucom add {} --update`",
+                template.as_asset().filename,
+                version_text(installed),
+                version_text(expected),
+                template.name()
+            ),
+            MessageType::Warning,
+        );
+    }
+}
+
+fn version_text(version: Option<u32>) -> String {
+    version.map_or_else(|| "unversioned".to_string(), |v| format!("version {v}"))
+}
+
+/// Removes the differences that don't matter when comparing scripts.
+fn normalize(content: &str) -> String {
+    let content = content.replace("\r\n", "\n");
+    content
+        .strip_prefix(NO_TEST_FRAMEWORK_DEFINE)
+        .and_then(|c| c.strip_prefix('\n'))
+        .map_or_else(|| content.clone(), ToString::to_string)
+}
+
+#[cfg(test)]
+mod builder_script_tests {
+    use super::*;
+
+    const TEMPLATE: &str = "// ucom-template-version: 2\nclass Builder {}\n";
+
+    #[test]
+    fn test_template_version() {
+        assert_eq!(template_version(TEMPLATE), Some(2));
+        assert_eq!(template_version("class Builder {}\n"), None);
+    }
+
+    #[test]
+    fn test_template_status() {
+        assert_eq!(
+            TemplateStatus::new(TEMPLATE, TEMPLATE),
+            TemplateStatus::UpToDate
+        );
+        assert_eq!(
+            TemplateStatus::new(&TEMPLATE.replace('\n', "\r\n"), TEMPLATE),
+            TemplateStatus::UpToDate
+        );
+        assert_eq!(
+            TemplateStatus::new(&format!("{NO_TEST_FRAMEWORK_DEFINE}\n{TEMPLATE}"), TEMPLATE),
+            TemplateStatus::UpToDate
+        );
+        assert_eq!(
+            TemplateStatus::new(
+                "// ucom-template-version: 2\nclass MyBuilder {}\n",
+                TEMPLATE
+            ),
+            TemplateStatus::Modified
+        );
+        assert_eq!(
+            TemplateStatus::new("class Builder {}\n", TEMPLATE),
+            TemplateStatus::VersionMismatch {
+                installed: None,
+                expected: Some(2)
+            }
+        );
+    }
+}
@@ -0,0 +1,107 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::anyhow;
+use itertools::Itertools;
+use uuid::Uuid;
+
+use crate::commands::AUTO_BUILD_SCRIPT_ROOT;
+use crate::commands::build_cmd::cleanup_csharp_build_script;
+use crate::unity::{ProjectPath, is_unity_editor_running};
+use crate::utils::path_ext::PlatformConsistentPathExt;
+use crate::utils::status_line::MessageType;
+
+/// Runs the clean command.
+pub fn clean_project(
+    project_dir: &Path,
+    stale_injections: bool,
+    dry_run: bool,
+) -> anyhow::Result<()> {
+    let project = ProjectPath::try_from(project_dir)?;
+
+    if stale_injections {
+        remove_stale_injections(&project, dry_run)?;
+    }
+    Ok(())
+}
+
+/// Removes the build scripts that were injected for a build that was interrupted.
+fn remove_stale_injections(project: &ProjectPath, dry_run: bool) -> anyhow::Result<()> {
+    // A running build has its injected script in the project.
+    if is_unity_editor_running(project)? {
+        return Err(anyhow!(
+            "Unity is running with this project, the injected build scripts may still be in use"
+        ));
+    }
+
+    let dirs = stale_injection_dirs(project)?;
+    if dirs.is_empty() {
+        MessageType::print_line("Clean", "no injected build scripts found", MessageType::Ok);
+        return Ok(());
+    }
+
+    for dir in dirs {
+        if !dry_run {
+            cleanup_csharp_build_script(&dir)?;
+        }
+
+        let relative_dir = dir.strip_prefix(&**project).unwrap_or(&dir);
+        MessageType::print_line(
+            if dry_run { "Would remove" } else { "Removed" },
+            relative_dir.normalized_display().to_string(),
+            MessageType::Ok,
+        );
+    }
+    Ok(())
+}
+
+/// Returns the directories of injected build scripts, including those of which only the meta file
+/// is left.
+fn stale_injection_dirs(project: &ProjectPath) -> anyhow::Result<Vec<PathBuf>> {
+    let root = Path::new(AUTO_BUILD_SCRIPT_ROOT);
+    let parent_dir = project.join(root.parent().unwrap_or(Path::new("")));
+    let prefix = root
+        .file_name()
+        .map(|name| format!("{}-", name.to_string_lossy()))
+        .unwrap_or_default();
+
+    Ok(fs::read_dir(&parent_dir)?
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            let name = name.strip_suffix(".meta").unwrap_or(&name);
+            is_injection_dir_name(name, &prefix).then(|| parent_dir.join(name))
+        })
+        .sorted()
+        .dedup()
+        .collect())
+}
+
+/// Checks if the name is that of an injected build script directory, e.g. `Ucom-<uuid>`.
+fn is_injection_dir_name(name: &str, prefix: &str) -> bool {
+    name.strip_prefix(prefix)
+        .is_some_and(|id| Uuid::parse_str(id).is_ok())
+}
+
+//
+// Tests
+//
+
+#[cfg(test)]
+mod clean_cmd_tests {
+    use super::*;
+
+    #[test]
+    fn test_is_injection_dir_name() {
+        assert!(is_injection_dir_name(
+            "Ucom-67e55044-10b1-426f-9247-bb680e5fe0c8",
+            "Ucom-"
+        ));
+        assert!(!is_injection_dir_name("Ucom-Scripts", "Ucom-"));
+        assert!(!is_injection_dir_name("Ucom", "Ucom-"));
+        assert!(!is_injection_dir_name(
+            "Plugins-67e55044-10b1-426f-9247-bb680e5fe0c8",
+            "Ucom-"
+        ));
+    }
+}
@@ -0,0 +1,75 @@
+use std::path::Path;
+
+use clap::{ArgMatches, CommandFactory, FromArgMatches};
+use yansi::Paint;
+
+use crate::cli::{Cli, OpenArguments};
+use crate::cli_build::BuildArguments;
+use crate::cli_new::NewArguments;
+use crate::cli_test::TestArguments;
+use crate::commands::INDENT;
+use crate::config::{
+    Config, Configurable, Origin, PROJECT_CONFIG_FILE, apply_loaded_config, user_config_path,
+};
+use crate::utils::path_ext::PlatformConsistentPathExt;
+
+/// Shows the resolved settings of the configurable commands and where each value comes from.
+pub fn show_config(project_dir: &Path) -> anyhow::Result<()> {
+    let project_file = project_dir.join(PROJECT_CONFIG_FILE);
+    let user_file = user_config_path();
+    let config = Config::from_files(Some(project_file.clone()), user_file.clone())?;
+
+    println!("{}", "Configuration files:".bold());
+    print_file_status(&config, &project_file, Origin::Project);
+    if let Some(user_file) = &user_file {
+        print_file_status(&config, user_file, Origin::User);
+    }
+
+    // Only the required arguments are given, the directories are not used to resolve the settings.
+    show_section::<BuildArguments>(&config, &["build"])?;
+    show_section::<TestArguments>(&config, &["test", "editmode"])?;
+    show_section::<OpenArguments>(&config, &["open"])?;
+    show_section::<NewArguments>(&config, &["new", "."])?;
+    Ok(())
+}
+
+fn print_file_status(config: &Config, path: &Path, origin: Origin) {
+    let status = if config.files().iter().any(|f| f.path == path) {
+        "found".green()
+    } else {
+        "not found".dim()
+    };
+    println!(
+        "{INDENT}{origin:<8} {} ({status})",
+        path.normalized_display()
+    );
+}
+
+/// Parses the command arguments without user input and prints the resolved settings.
+fn show_section<T: Configurable + FromArgMatches>(
+    config: &Config,
+    args: &[&str],
+) -> anyhow::Result<()> {
+    let matches =
+        Cli::command().try_get_matches_from(std::iter::once("ucom").chain(args.iter().copied()))?;
+    let sub_matches = subcommand_matches(&matches);
+    let mut arguments = T::from_arg_matches(sub_matches)?;
+    let settings = apply_loaded_config(&mut arguments, sub_matches, config)?;
+
+    println!();
+    println!("{}", format!("[{}]", T::SECTION).bold());
+    for setting in settings {
+        let origin = format!("({})", setting.origin);
+        let origin = if setting.origin == Origin::Default {
+            origin.dim()
+        } else {
+            origin.cyan()
+        };
+        println!("{INDENT}{} = {} {origin}", setting.key, setting.value);
+    }
+    Ok(())
+}
+
+fn subcommand_matches(matches: &ArgMatches) -> &ArgMatches {
+    matches.subcommand().map_or(matches, |(_, sub)| sub)
+}
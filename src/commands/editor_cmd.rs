@@ -0,0 +1,58 @@
+use std::path::Path;
+use std::time::Duration;
+
+use anyhow::anyhow;
+
+use crate::cli::EditorAction;
+use crate::cli_build::BuildScriptTarget;
+use crate::commands::editor_ipc::{EditorChannel, EditorCommand, EditorResult};
+use crate::unity::ProjectPath;
+use crate::utils::path_ext::PlatformConsistentPathExt;
+use crate::utils::status_line::MessageType;
+
+/// Runs the editor command, which executes the action in the editor that has the project open.
+pub fn editor_command(
+    project_dir: &Path,
+    action: &EditorAction,
+    timeout: Option<Duration>,
+) -> anyhow::Result<()> {
+    let project = ProjectPath::try_from(project_dir)?;
+
+    let Some(channel) = EditorChannel::open(&project)? else {
+        return Err(anyhow!(
+            "No Unity editor has the project open: {}",
+            project.normalized_display()
+        ));
+    };
+
+    let command = editor_command_for(action);
+    let result: EditorResult = channel.send(&command)?.wait_for_result(timeout)?;
+
+    match result.status.as_str() {
+        "success" => {
+            MessageType::print_line("Succeeded", &result.message, MessageType::Ok);
+            Ok(())
+        }
+        _ => Err(anyhow!(
+            "{} ({})",
+            result.message,
+            result.error_code.as_deref().unwrap_or("UNKNOWN_ERROR")
+        )),
+    }
+}
+
+fn editor_command_for(action: &EditorAction) -> EditorCommand {
+    match action {
+        EditorAction::Refresh => EditorCommand::Refresh,
+        EditorAction::SwitchPlatform { target } => EditorCommand::SwitchPlatform {
+            platform: BuildScriptTarget::from(*target).as_ref().to_string(),
+        },
+        EditorAction::ExecuteMethod { method, args } => EditorCommand::ExecuteMethod {
+            method: method.clone(),
+            args: args.clone(),
+        },
+        EditorAction::EnterPlaymode => EditorCommand::EnterPlaymode,
+        EditorAction::ExitPlaymode => EditorCommand::ExitPlaymode,
+        EditorAction::SaveScenes => EditorCommand::SaveScenes,
+    }
+}
@@ -0,0 +1,749 @@
+use std::fs;
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::sync::mpsc::Receiver;
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use anyhow::{Context, anyhow};
+use chrono::Utc;
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use strum::AsRefStr;
+use sysinfo::{Pid, ProcessesToUpdate, System};
+use uuid::Uuid;
+
+use crate::cli_add::UnityTemplateFile;
+use crate::commands::PERSISTENT_BUILD_SCRIPT_ROOT;
+use crate::commands::builder_script::ensure_compatible_builder_script;
+use crate::unity::diagnostics::{Diagnostic, DiagnosticKind, Location, Severity};
+use crate::unity::{CommandError, ProjectPath, TIMEOUT_EXIT_CODE, find_unity_editor_process};
+use crate::utils::format_duration;
+use crate::utils::interrupt::CleanupGuard;
+use crate::utils::path_ext::PlatformConsistentPathExt;
+
+/// The directory, relative to the project, that the editor watches for command files.
+const COMMAND_DIR: &str = "Temp/ucom-commands";
+
+/// The directory, relative to the project, in which the editor writes the results.
+const RESULT_DIR: &str = "Temp/ucom-results";
+
+/// The file in the result directory that the editor regularly updates while it watches for commands.
+const HEARTBEAT_FILE: &str = "heartbeat";
+
+/// How long the heartbeat may be out of date before the editor is considered to be hanging.
+///
+/// The heartbeat is not updated while scripts are reloaded, which can take a while in large projects.
+const HEARTBEAT_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// How often the editor and the heartbeat are checked while waiting for a result. The result
+/// directory is also checked at this interval when file system notifications are not available.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// A command that the `EditorCommandWatcher` executes in the editor.
+///
+/// The command is written as a flat JSON object, with the kebab-case name of the variant in the
+/// `command` field.
+#[derive(Serialize, AsRefStr, Debug)]
+#[serde(tag = "command", rename_all = "kebab-case")]
+#[strum(serialize_all = "kebab-case")]
+pub enum EditorCommand {
+    /// Builds the player.
+    Build(BuildCommand),
+    /// Runs tests with the Test Runner API.
+    Test(TestCommand),
+    /// Imports changed assets with `AssetDatabase.Refresh`.
+    Refresh,
+    /// Switches the active build target, `platform` is a `UnityEditor.BuildTarget` name.
+    SwitchPlatform {
+        platform: String,
+    },
+    /// Invokes a static method, `method` is the full name of the type followed by the method name.
+    ExecuteMethod {
+        method: String,
+        args: Vec<String>,
+    },
+    EnterPlaymode,
+    ExitPlaymode,
+    /// Saves the open scenes.
+    SaveScenes,
+}
+
+/// The arguments of the build command.
+#[derive(Serialize, Debug)]
+pub struct BuildCommand {
+    pub platform: String,
+    /// Path of the build profile asset, empty when building for a platform.
+    pub build_profile: String,
+    pub output_path: String,
+    pub log_path: String,
+    pub build_options: i32,
+    /// Scripting define symbols to add to the build.
+    pub defines: Vec<String>,
+    /// Passed to the method with the `[UcomPreProcessBuild]` attribute.
+    pub build_args: String,
+    /// The static method that builds the player, the editor builds it itself for the default
+    /// `Ucom.UnityBuilder.Build`.
+    pub build_function: String,
+    /// The arguments that a batch mode build would get on the command line, including the extra
+    /// Unity arguments. The build function reads them with `UnityBuilder.GetCommandLineArgs`.
+    pub args: Vec<String>,
+    pub development_build: bool,
+    pub force_platform_switch: bool,
+    pub force_play_mode_exit: bool,
+}
+
+/// The arguments of the test command.
+#[derive(Serialize, Debug)]
+pub struct TestCommand {
+    /// `EditMode`, `PlayMode` or the `UnityEditor.BuildTarget` name of a player to run the tests in.
+    pub platform: String,
+    pub categories: Vec<String>,
+    /// Full names or regular expressions of the tests to run.
+    pub tests: Vec<String>,
+    pub assemblies: Vec<String>,
+    /// The path of the NUnit XML file the editor writes the test results to.
+    pub results_path: String,
+}
+
+/// The file that is sent to the editor, the command together with its identification.
+#[derive(Serialize)]
+struct CommandFile<'a> {
+    uuid: String,
+    timestamp: String,
+    #[serde(flatten)]
+    command: &'a EditorCommand,
+}
+
+/// The result of a command that only reports whether it succeeded.
+#[derive(Deserialize, Debug)]
+pub struct EditorResult {
+    /// Either `success`, `failed` when the command failed or `error` when it could not be executed.
+    pub status: String,
+    pub message: String,
+    pub error_code: Option<String>,
+}
+
+/// An error or warning that the editor reported while executing a command.
+#[derive(Deserialize, Debug, Clone)]
+pub struct EditorMessage {
+    /// Either `error` or `warning`.
+    pub severity: String,
+    pub message: String,
+    /// The compiler error code, e.g. `CS0103`, empty for other messages.
+    #[serde(default)]
+    pub code: String,
+    /// The file as reported by Unity, empty when the message has no location.
+    #[serde(default)]
+    pub file: String,
+    #[serde(default)]
+    pub line: u32,
+    #[serde(default)]
+    pub column: u32,
+}
+
+impl EditorMessage {
+    pub fn is_error(&self) -> bool {
+        self.severity == "error"
+    }
+}
+
+impl From<&EditorMessage> for Diagnostic {
+    fn from(message: &EditorMessage) -> Self {
+        Self {
+            severity: if message.is_error() {
+                Severity::Error
+            } else {
+                Severity::Warning
+            },
+            kind: if message.code.is_empty() {
+                DiagnosticKind::Build
+            } else {
+                DiagnosticKind::Compiler {
+                    code: message.code.clone(),
+                }
+            },
+            message: message.message.clone(),
+            location: (!message.file.is_empty() && message.line > 0).then(|| Location {
+                file: PathBuf::from(&message.file),
+                line: message.line,
+                column: (message.column > 0).then_some(message.column),
+            }),
+            stack_trace: Vec::new(),
+        }
+    }
+}
+
+/// An event that the editor appends to the progress file of a command while executing it, one
+/// JSON object per line.
+#[derive(Deserialize, Debug)]
+#[serde(tag = "kind", rename_all = "kebab-case")]
+pub enum ProgressEvent {
+    /// The command entered a new phase, e.g. processing the scenes of a build. `percentage` is
+    /// negative when the progress within the phase is unknown.
+    Phase { phase: String, percentage: f32 },
+    /// An error or warning, which is also included in the result.
+    Message(EditorMessage),
+}
+
+/// The file-based channel to the `EditorCommandWatcher` in `UnityBuilder.cs`, which executes
+/// commands in an editor that has the project open.
+pub struct EditorChannel {
+    command_dir: PathBuf,
+    result_dir: PathBuf,
+    editor_pid: Pid,
+    /// A heartbeat written before this time is left over from an earlier session.
+    editor_start_time: SystemTime,
+}
+
+/// A command that was sent to the editor. Its files are removed when it is dropped.
+pub struct PendingCommand<'a> {
+    channel: &'a EditorChannel,
+    uuid: Uuid,
+    command_file: PathBuf,
+    result_file: PathBuf,
+    progress: ProgressReader,
+    heartbeat: HeartbeatMonitor,
+    /// Cancels the command when ucom is interrupted while waiting for the result.
+    interrupt_guard: Option<CleanupGuard>,
+}
+
+impl EditorChannel {
+    /// Opens the channel to the editor that has the project open, if there is one.
+    ///
+    /// Returns an error if the editor is running, but the project doesn't have the builder script
+    /// that contains the command watcher.
+    pub fn open(project: &ProjectPath) -> anyhow::Result<Option<Self>> {
+        let Some(editor_pid) = find_unity_editor_process(project) else {
+            return Ok(None);
+        };
+
+        let builder_file_name = UnityTemplateFile::Builder.as_asset().filename;
+        if !project
+            .join(PERSISTENT_BUILD_SCRIPT_ROOT)
+            .join(builder_file_name)
+            .exists()
+        {
+            return Err(anyhow!(
+                "Unity editor is running, but {} not installed.\n\n\
+                 To send commands to the running editor, install the builder script:\n\
+                 \n\
+                 {}\n\
+                 \n\
+                 This will enable both batch mode and editor IPC builds.\n\
+                 Or close the Unity editor to build in batch mode.",
+                builder_file_name,
+                format_args!("  ucom add builder {}", project.normalized_display())
+            ));
+        }
+
+        ensure_compatible_builder_script(project)?;
+
+        let mut system = System::new();
+        system.refresh_processes(ProcessesToUpdate::Some(&[editor_pid]), true);
+        let editor_start_time = system.process(editor_pid).map_or(UNIX_EPOCH, |p| {
+            UNIX_EPOCH + Duration::from_secs(p.start_time())
+        });
+
+        Ok(Some(Self {
+            command_dir: project.join(COMMAND_DIR),
+            result_dir: project.join(RESULT_DIR),
+            editor_pid,
+            editor_start_time,
+        }))
+    }
+
+    /// Writes the command file, which is named after the command and its uuid.
+    pub fn send(&self, command: &EditorCommand) -> anyhow::Result<PendingCommand<'_>> {
+        fs::create_dir_all(&self.command_dir)?;
+        fs::create_dir_all(&self.result_dir)?;
+
+        let uuid = Uuid::new_v4();
+        let file_name = format!("{}-{uuid}.json", command.as_ref());
+        let command_file = self.command_dir.join(&file_name);
+        let result_file = self.result_dir.join(&file_name);
+        let progress_file = self
+            .result_dir
+            .join(format!("{}-{uuid}.jsonl", command.as_ref()));
+
+        let interrupt_guard = {
+            let command_dir = self.command_dir.clone();
+            let command_file = command_file.clone();
+            let result_file = result_file.clone();
+            CleanupGuard::new(move || {
+                if let Err(e) = cancel_command(&command_dir, &command_file, &result_file, uuid) {
+                    eprintln!("{e:#}");
+                }
+            })
+        };
+
+        let command_file_content = CommandFile {
+            uuid: uuid.to_string(),
+            timestamp: Utc::now().to_rfc3339(),
+            command,
+        };
+
+        write_atomically(
+            &command_file,
+            &serde_json::to_string_pretty(&command_file_content)?,
+        )
+        .context("Cannot send the command to the editor")?;
+
+        Ok(PendingCommand {
+            channel: self,
+            uuid,
+            command_file,
+            result_file,
+            progress: ProgressReader::new(progress_file),
+            heartbeat: HeartbeatMonitor::new(
+                self.result_dir.join(HEARTBEAT_FILE),
+                self.editor_start_time,
+            ),
+            interrupt_guard: Some(interrupt_guard),
+        })
+    }
+
+    fn has_editor_exited(&self, system: &mut System) -> bool {
+        system.refresh_processes(ProcessesToUpdate::Some(&[self.editor_pid]), true);
+        system.process(self.editor_pid).is_none()
+    }
+}
+
+impl PendingCommand<'_> {
+    /// Waits until the editor has written the result of the command.
+    ///
+    /// The command is cancelled when the editor exits, stops responding or when the timeout
+    /// expires.
+    pub fn wait_for_result<R: DeserializeOwned>(
+        self,
+        timeout: Option<Duration>,
+    ) -> anyhow::Result<R> {
+        self.wait_for_result_with_progress(timeout, |_| {})
+    }
+
+    /// Waits until the editor has written the result of the command, passing the events from the
+    /// progress file to `on_progress` as they are appended.
+    pub fn wait_for_result_with_progress<R: DeserializeOwned>(
+        mut self,
+        timeout: Option<Duration>,
+        mut on_progress: impl FnMut(ProgressEvent),
+    ) -> anyhow::Result<R> {
+        let start = Instant::now();
+        let mut system = System::new();
+        let watcher = ResultDirWatcher::new(&self.channel.result_dir);
+
+        loop {
+            let result_exists = self.result_file.exists();
+
+            // The editor finishes the progress file before it writes the result.
+            self.progress
+                .read_events()
+                .into_iter()
+                .for_each(&mut on_progress);
+
+            if result_exists {
+                let json = fs::read_to_string(&self.result_file)?;
+                return Ok(serde_json::from_str(&json)?);
+            }
+
+            // The command files are removed when dropped, there is no editor left to cancel it.
+            if self.channel.has_editor_exited(&mut system) {
+                return Err(anyhow!("The Unity editor has exited"));
+            }
+
+            if let Err(e) = self.heartbeat.check() {
+                self.cancel();
+                return Err(e);
+            }
+
+            if let Some(timeout) = timeout.filter(|&t| start.elapsed() >= t) {
+                self.cancel();
+                return Err(CommandError {
+                    exit_code: TIMEOUT_EXIT_CODE,
+                    stderr: format!(
+                        "Timed out after {} waiting for the Unity editor, the command was cancelled",
+                        format_duration(timeout)
+                    ),
+                    timed_out: true,
+                }
+                .into());
+            }
+
+            watcher.wait(POLL_INTERVAL);
+        }
+    }
+
+    /// Asks the editor to cancel the command.
+    fn cancel(&mut self) {
+        drop(self.interrupt_guard.take());
+        if let Err(e) = cancel_command(
+            &self.channel.command_dir,
+            &self.command_file,
+            &self.result_file,
+            self.uuid,
+        ) {
+            eprintln!("{e:#}");
+        }
+    }
+}
+
+impl Drop for PendingCommand<'_> {
+    fn drop(&mut self) {
+        drop(self.interrupt_guard.take());
+        let _ = fs::remove_file(&self.command_file);
+        let _ = fs::remove_file(&self.result_file);
+        let _ = fs::remove_file(&self.progress.path);
+    }
+}
+
+/// Reads the events that were appended to a progress file since the last read.
+struct ProgressReader {
+    path: PathBuf,
+    offset: u64,
+    /// The start of a line that the editor has not finished writing yet.
+    partial_line: Vec<u8>,
+}
+
+impl ProgressReader {
+    fn new(path: PathBuf) -> Self {
+        Self {
+            path,
+            offset: 0,
+            partial_line: Vec::new(),
+        }
+    }
+
+    /// Returns the events of the lines that were completed since the last read. Lines that cannot
+    /// be parsed are skipped, progress is only informational.
+    fn read_events(&mut self) -> Vec<ProgressEvent> {
+        let Ok(mut file) = File::open(&self.path) else {
+            return Vec::new();
+        };
+
+        let mut appended = Vec::new();
+        if file.seek(SeekFrom::Start(self.offset)).is_err()
+            || file.read_to_end(&mut appended).is_err()
+        {
+            return Vec::new();
+        }
+        self.offset += appended.len() as u64;
+        self.partial_line.extend(appended);
+
+        let Some(end) = self.partial_line.iter().rposition(|&b| b == b'\n') else {
+            return Vec::new();
+        };
+        let lines: Vec<u8> = self.partial_line.drain(..=end).collect();
+
+        String::from_utf8_lossy(&lines)
+            .lines()
+            .filter_map(|line| serde_json::from_str(line.trim()).ok())
+            .collect()
+    }
+}
+
+/// Watches the heartbeat that the editor writes while it watches for commands.
+struct HeartbeatMonitor {
+    path: PathBuf,
+    editor_start_time: SystemTime,
+    last_modified: Option<SystemTime>,
+    /// When the command was sent or the heartbeat was last seen to change.
+    last_change: Instant,
+}
+
+impl HeartbeatMonitor {
+    /// Starts monitoring when the command is sent, an older heartbeat doesn't count against it.
+    /// The editor has until the timeout to write its first heartbeat.
+    fn new(path: PathBuf, editor_start_time: SystemTime) -> Self {
+        let mut monitor = Self {
+            path,
+            editor_start_time,
+            last_modified: None,
+            last_change: Instant::now(),
+        };
+        monitor.last_modified = monitor.fresh_heartbeat();
+        monitor
+    }
+
+    /// Returns an error if the editor has not updated its heartbeat for too long since the
+    /// command was sent. A missing heartbeat counts as one that doesn't change, the command
+    /// watcher of the editor may never have started.
+    fn check(&mut self) -> anyhow::Result<()> {
+        let modified = self.fresh_heartbeat();
+        if modified.is_some() && modified != self.last_modified {
+            self.last_modified = modified;
+            self.last_change = Instant::now();
+            return Ok(());
+        }
+
+        let silence = self.last_change.elapsed();
+        if silence > HEARTBEAT_TIMEOUT {
+            return Err(anyhow!(
+                "The Unity editor has not responded for {}, it may be hanging or have script \
+                 compilation errors",
+                format_duration(silence)
+            ));
+        }
+        Ok(())
+    }
+
+    /// Returns when the heartbeat was last written, if it was written by the running editor. A
+    /// heartbeat written before the editor started is left over from a previous session.
+    fn fresh_heartbeat(&self) -> Option<SystemTime> {
+        fs::metadata(&self.path)
+            .and_then(|md| md.modified())
+            .ok()
+            .filter(|&modified| modified >= self.editor_start_time)
+    }
+}
+
+/// Removes the command file, so the editor doesn't pick it up anymore, and sends a cancel command
+/// for when it already has.
+fn cancel_command(
+    command_dir: &Path,
+    command_file: &Path,
+    result_file: &Path,
+    uuid: Uuid,
+) -> anyhow::Result<()> {
+    let _ = fs::remove_file(command_file);
+    let _ = fs::remove_file(result_file);
+
+    let cancel = json!({ "command": "cancel", "uuid": uuid.to_string() });
+    write_atomically(
+        &command_dir.join(format!("cancel-{uuid}.json")),
+        &serde_json::to_string_pretty(&cancel)?,
+    )
+    .context("Cannot send the cancel command to the editor")
+}
+
+/// Writes the file under a temporary name and renames it, so the editor never reads a partially
+/// written command. The temporary name doesn't end with `.json`, which the editor watches for.
+fn write_atomically(path: &Path, contents: &str) -> std::io::Result<()> {
+    let mut temp_name = path.file_name().unwrap_or_default().to_os_string();
+    temp_name.push(".tmp");
+    let temp_path = path.with_file_name(temp_name);
+
+    fs::write(&temp_path, contents)?;
+    fs::rename(&temp_path, path).inspect_err(|_| {
+        let _ = fs::remove_file(&temp_path);
+    })
+}
+
+/// Wakes up the wait for a result as soon as a file in the result directory changes.
+struct ResultDirWatcher {
+    /// `None` when the file system doesn't support notifications, then the directory is polled.
+    watcher: Option<RecommendedWatcher>,
+    changes: Receiver<()>,
+}
+
+impl ResultDirWatcher {
+    fn new(result_dir: &Path) -> Self {
+        let (sender, changes) = mpsc::channel();
+        let watcher = notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+            if event.is_ok() {
+                let _ = sender.send(());
+            }
+        })
+        .and_then(|mut watcher| {
+            watcher
+                .watch(result_dir, RecursiveMode::NonRecursive)
+                .map(|()| watcher)
+        })
+        .ok();
+
+        Self { watcher, changes }
+    }
+
+    /// Waits until a file in the result directory changes, at most for the timeout.
+    fn wait(&self, timeout: Duration) {
+        if self.watcher.is_none() {
+            thread::sleep(timeout);
+            return;
+        }
+
+        if self.changes.recv_timeout(timeout).is_ok() {
+            // A single write can cause several events, they are handled at once.
+            self.changes.try_iter().for_each(drop);
+        }
+    }
+}
+
+#[cfg(test)]
+mod editor_ipc_tests {
+    use super::*;
+
+    #[test]
+    fn test_command_file_is_flat() {
+        let command = EditorCommand::ExecuteMethod {
+            method: "Tools.Bake".to_string(),
+            args: vec!["--fast".to_string()],
+        };
+        let json = serde_json::to_value(CommandFile {
+            uuid: "1234".to_string(),
+            timestamp: "2024-01-01T00:00:00Z".to_string(),
+            command: &command,
+        })
+        .unwrap();
+
+        assert_eq!(command.as_ref(), "execute-method");
+        assert_eq!(
+            json,
+            json!({
+                "uuid": "1234",
+                "timestamp": "2024-01-01T00:00:00Z",
+                "command": "execute-method",
+                "method": "Tools.Bake",
+                "args": ["--fast"],
+            })
+        );
+    }
+
+    #[test]
+    fn test_read_progress_events() {
+        let path = std::env::temp_dir().join(format!("ucom-progress-{}.jsonl", Uuid::new_v4()));
+        let mut reader = ProgressReader::new(path.clone());
+        assert!(reader.read_events().is_empty());
+
+        fs::write(
+            &path,
+            concat!(
+                r#"{"kind":"phase","phase":"Processing scenes","percentage":50.0}"#,
+                "\n",
+                r#"{"kind":"message","severity":"error","message":"The name 'x' does not exist","#,
+            ),
+        )
+        .unwrap();
+        let events = reader.read_events();
+        assert!(matches!(
+            events.as_slice(),
+            [ProgressEvent::Phase { phase, percentage }] if phase == "Processing scenes" && *percentage == 50.0
+        ));
+
+        let mut file = fs::OpenOptions::new().append(true).open(&path).unwrap();
+        std::io::Write::write_all(
+            &mut file,
+            br#""code":"CS0103","file":"Assets/Player.cs","line":12,"column":5}
+"#,
+        )
+        .unwrap();
+        let events = reader.read_events();
+        let [ProgressEvent::Message(message)] = events.as_slice() else {
+            panic!("expected a message: {events:?}");
+        };
+        let diagnostic = Diagnostic::from(message);
+        assert_eq!(diagnostic.severity, Severity::Error);
+        assert_eq!(
+            diagnostic.to_string(),
+            "Assets/Player.cs:12:5: The name 'x' does not exist"
+        );
+        assert!(reader.read_events().is_empty());
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_build_command_carries_build_arguments() {
+        let command = EditorCommand::Build(BuildCommand {
+            platform: "Android".to_string(),
+            build_profile: String::new(),
+            output_path: "Builds/Release/android".to_string(),
+            log_path: "Logs/build.log".to_string(),
+            build_options: 1,
+            defines: vec!["CHEATS".to_string()],
+            build_args: "1.2.3".to_string(),
+            build_function: "Company.Build.Run".to_string(),
+            args: vec!["-customArg".to_string()],
+            development_build: true,
+            force_platform_switch: false,
+            force_play_mode_exit: false,
+        });
+        let json = serde_json::to_value(&command).unwrap();
+
+        assert_eq!(json["command"], "build");
+        assert_eq!(json["build_args"], "1.2.3");
+        assert_eq!(json["build_function"], "Company.Build.Run");
+        assert_eq!(json["args"], json!(["-customArg"]));
+        assert_eq!(json["log_path"], "Logs/build.log");
+    }
+
+    #[test]
+    fn test_write_atomically() {
+        let dir = std::env::temp_dir().join(format!("ucom-ipc-{}", Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("build-1234.json");
+
+        write_atomically(&path, "{}").unwrap();
+        write_atomically(&path, r#"{"command":"build"}"#).unwrap();
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), r#"{"command":"build"}"#);
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 1);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_heartbeat_is_stale_relative_to_the_command() {
+        let dir = std::env::temp_dir().join(format!("ucom-ipc-{}", Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(HEARTBEAT_FILE);
+        let hour = Duration::from_secs(3600);
+        File::create(&path)
+            .unwrap()
+            .set_modified(SystemTime::now() - hour)
+            .unwrap();
+        let Some(long_ago) = Instant::now().checked_sub(HEARTBEAT_TIMEOUT * 2) else {
+            return;
+        };
+
+        // An old heartbeat only becomes stale once it hasn't changed since the command was sent.
+        let mut monitor = HeartbeatMonitor::new(path.clone(), SystemTime::now() - 2 * hour);
+        assert!(monitor.check().is_ok());
+        monitor.last_change = long_ago;
+        assert!(monitor.check().is_err());
+
+        // A heartbeat from before the editor started doesn't count as a response.
+        let mut monitor = HeartbeatMonitor::new(path.clone(), SystemTime::now());
+        assert!(monitor.check().is_ok());
+        monitor.last_change = long_ago;
+        assert!(monitor.check().is_err());
+
+        // Neither does a missing heartbeat, but one that appears in time does.
+        fs::remove_file(&path).unwrap();
+        let mut monitor = HeartbeatMonitor::new(path.clone(), SystemTime::now() - 2 * hour);
+        assert!(monitor.check().is_ok());
+        monitor.last_change = long_ago;
+        assert!(monitor.check().is_err());
+        fs::write(&path, "").unwrap();
+        assert!(monitor.check().is_ok());
+        monitor.last_change = long_ago;
+        assert!(monitor.check().is_err());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_result_dir_watcher_wakes_up_on_change() {
+        let dir = std::env::temp_dir().join(format!("ucom-ipc-{}", Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        let watcher = ResultDirWatcher::new(&dir);
+
+        let start = Instant::now();
+        write_atomically(&dir.join("build-1234.json"), "{}").unwrap();
+        watcher.wait(Duration::from_secs(5));
+
+        // Without file system notifications, the wait always lasts for the whole timeout.
+        if watcher.watcher.is_some() {
+            assert!(start.elapsed() < Duration::from_secs(5));
+        }
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_unit_command_names() {
+        let json = serde_json::to_value(EditorCommand::EnterPlaymode).unwrap();
+        assert_eq!(json, json!({ "command": "enter-playmode" }));
+        assert_eq!(EditorCommand::SaveScenes.as_ref(), "save-scenes");
+    }
+}
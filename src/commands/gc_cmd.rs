@@ -0,0 +1,162 @@
+use std::collections::BTreeSet;
+use std::path::PathBuf;
+
+use anyhow::anyhow;
+use yansi::Paint;
+
+use crate::commands::uninstall_cmd::remove_installation;
+use crate::unity::installations::Installations;
+use crate::unity::{ProjectPath, Version, walk_visible_directories};
+use crate::utils;
+use crate::utils::path_ext::PlatformConsistentPathExt;
+use crate::utils::status_line::{MessageType, StatusLine};
+use crate::utils::{confirm, dir_size, format_size};
+
+/// Maximum depth to search for projects in the root directories.
+const PROJECT_SEARCH_DEPTH: usize = 5;
+
+/// The projects found in the root directories.
+struct UsedVersions {
+    project_count: usize,
+    versions: BTreeSet<Version>,
+    /// Projects whose version cannot be read, they may use any of the installed versions.
+    unreadable: Vec<(PathBuf, anyhow::Error)>,
+}
+
+/// Lists the installed versions that are not used by any project in the root directories
+/// and removes them after confirmation.
+///
+/// Nothing is removed when the version of a project cannot be read, unless `ignore_unreadable`
+/// is set.
+pub fn collect_unused_versions(
+    roots: &[PathBuf],
+    yes: bool,
+    dry_run: bool,
+    ignore_unreadable: bool,
+) -> anyhow::Result<()> {
+    let installed = Installations::find_installations(None)?;
+
+    let UsedVersions {
+        project_count,
+        versions: used_versions,
+        unreadable,
+    } = find_used_versions(roots)?;
+    MessageType::print_line(
+        "Found",
+        format!(
+            "{project_count} project(s) using {} version(s)",
+            used_versions.len()
+        ),
+        MessageType::Info,
+    );
+
+    for (path, e) in &unreadable {
+        MessageType::print_line(
+            "Warning",
+            format!(
+                "Cannot read the Unity version of {}: {e:#}",
+                path.normalized_display()
+            ),
+            MessageType::Warning,
+        );
+    }
+
+    let unused = {
+        let _status = StatusLine::new("Calculating", "size of unused versions");
+        installed
+            .versions
+            .iter()
+            .filter(|v| !used_versions.contains(v))
+            .filter_map(|v| installed.location(*v).map(|l| (*v, l, dir_size(&l.dir))))
+            .collect::<Vec<_>>()
+    };
+
+    if unused.is_empty() {
+        MessageType::print_line(
+            "Finished",
+            "All installed versions are in use",
+            MessageType::Ok,
+        );
+        return Ok(());
+    }
+
+    println!("Unused Unity versions:");
+    for (version, location, size) in &unused {
+        println!(
+            "  {} {:>10}  {}",
+            format!("{version:<14}").bold(),
+            format_size(*size),
+            location.dir.normalized_display()
+        );
+    }
+
+    let total_size: u64 = unused.iter().map(|(_, _, size)| size).sum();
+    if dry_run {
+        return Ok(());
+    }
+
+    if !unreadable.is_empty() && !ignore_unreadable {
+        return Err(anyhow!(
+            "The version of {} project(s) cannot be read, they may use one of the unused versions. \
+             Fix the projects or remove the versions anyway with --ignore-unreadable",
+            unreadable.len()
+        ));
+    }
+
+    if !yes
+        && !confirm(format!(
+            "Remove {} unused version(s) ({})?",
+            unused.len(),
+            format_size(total_size)
+        ))?
+    {
+        return Ok(());
+    }
+
+    for (version, location, _) in &unused {
+        remove_installation(*version, location)?;
+        MessageType::print_line("Removed", format!("Unity {version}"), MessageType::Ok);
+    }
+
+    MessageType::print_line(
+        "Finished",
+        format!("freed {}", format_size(total_size)),
+        MessageType::Ok,
+    );
+    Ok(())
+}
+
+/// Returns the projects in the root directories and the versions they use. Directories that
+/// cannot be read are skipped.
+fn find_used_versions(roots: &[PathBuf]) -> anyhow::Result<UsedVersions> {
+    let _status = StatusLine::new("Searching", "for Unity projects");
+    let mut used = UsedVersions {
+        project_count: 0,
+        versions: BTreeSet::new(),
+        unreadable: Vec::new(),
+    };
+
+    for root in roots {
+        let root = utils::resolve_absolute_dir_path(root)?;
+        let mut directories = walk_visible_directories(root, PROJECT_SEARCH_DEPTH);
+
+        while let Some(entry) = directories.next() {
+            let Ok(entry) = entry else {
+                continue;
+            };
+
+            if let Ok(project) = ProjectPath::try_from(entry.path()) {
+                match project.unity_version() {
+                    Ok(version) => {
+                        used.versions.insert(version);
+                    }
+                    Err(e) => used.unreadable.push((entry.path().to_path_buf(), e)),
+                }
+                used.project_count += 1;
+                directories.skip_current_dir();
+            }
+        }
+    }
+
+    Ok(used)
+}
@@ -1,5 +1,7 @@
 use crate::cli::PackagesInfoLevel;
-use crate::commands::{MARK_AVAILABLE, MARK_ERROR, MARK_UNAVAILABLE, install_latest_matching};
+use crate::commands::{
+    InstallOptions, MARK_AVAILABLE, MARK_ERROR, MARK_UNAVAILABLE, install_latest_matching,
+};
 use crate::style_definitions::*;
 use crate::unity::project::*;
 use crate::unity::release_api::{UpdatePolicy, fetch_latest_releases};
@@ -82,7 +84,11 @@ fn show_project_info(
     if !version.is_editor_installed()? {
         report.blank_line();
         if install_required {
-            install_latest_matching(version.to_interned_str(), mode)?;
+            install_latest_matching(
+                version.to_interned_str(),
+                mode,
+                &InstallOptions::unity_hub(),
+            )?;
         } else {
             report.paragraph(format_args!(
                 "Use the `{op}` flag to install Unity version {ve}",
@@ -1,10 +1,50 @@
 use crate::style_definitions::LINK;
-use crate::unity::release_api::{UpdatePolicy, fetch_latest_releases};
+use crate::unity::installations::Installations;
+use crate::unity::installer::{
+    Module, host_editor_download, install_editor, install_module, resolve_modules,
+};
+use crate::unity::release_api::{UpdatePolicy, fetch_latest_releases, fetch_release};
 use crate::unity::release_api_data::ReleaseData;
+use crate::utils::path_ext::PlatformConsistentPathExt;
+use crate::utils::status_line::{MessageType, StatusLine};
 use anyhow::anyhow;
 use yansi::Paint;
 
-pub fn install_latest_matching(version_prefix: &str, mode: UpdatePolicy) -> anyhow::Result<()> {
+/// How an editor is installed.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum InstallMethod {
+    /// Download and extract the editor directly.
+    #[default]
+    Download,
+    /// Let Unity Hub install the editor through a deep link.
+    UnityHub,
+}
+
+/// Options for installing an editor.
+#[derive(Debug, Default)]
+pub struct InstallOptions {
+    pub method: InstallMethod,
+    /// Ids of the build support modules to install.
+    pub modules: Vec<String>,
+    /// Accept the EULAs of the modules to install.
+    pub accept_eula: bool,
+}
+
+impl InstallOptions {
+    /// Installs the editor only, through Unity Hub.
+    pub fn unity_hub() -> Self {
+        Self {
+            method: InstallMethod::UnityHub,
+            ..Self::default()
+        }
+    }
+}
+
+pub fn install_latest_matching(
+    version_prefix: &str,
+    mode: UpdatePolicy,
+    options: &InstallOptions,
+) -> anyhow::Result<()> {
     let releases = fetch_latest_releases(mode)?;
     let release = releases
         .iter()
@@ -12,14 +52,102 @@ pub fn install_latest_matching(version_prefix: &str, mode: UpdatePolicy) -> anyh
         .max_by_key(|rd| rd.version)
         .ok_or_else(|| anyhow!("No version found that matches `{version_prefix}`"))?;
 
-    install_version(release)
+    install_version(release, options)
 }
 
-pub fn install_version(release: &ReleaseData) -> anyhow::Result<()> {
-    if release.version.is_editor_installed()? {
+pub fn install_version(release: &ReleaseData, options: &InstallOptions) -> anyhow::Result<()> {
+    let is_installed = release.version.is_editor_installed()?;
+    if is_installed && options.modules.is_empty() {
         anyhow::bail!("Version {} is already installed", release.version);
     }
 
+    match options.method {
+        InstallMethod::Download => download_and_install(release, options, is_installed),
+        InstallMethod::UnityHub if !options.modules.is_empty() => {
+            anyhow::bail!("Modules cannot be installed through Unity Hub")
+        }
+        InstallMethod::UnityHub => open_unity_hub(release),
+    }
+}
+
+/// Downloads the editor and modules for the host platform and installs them in the editor root directory.
+/// If the editor is already installed, only the modules are installed.
+fn download_and_install(
+    release: &ReleaseData,
+    options: &InstallOptions,
+    is_installed: bool,
+) -> anyhow::Result<()> {
+    // The cached release data does not contain the download information.
+    let fetched;
+    let release = if release.downloads.is_some() {
+        release
+    } else {
+        let _status = StatusLine::new("Fetching", format!("Unity {} downloads", release.version));
+        fetched = fetch_release(release.version)?;
+        &fetched
+    };
+
+    let modules = resolve_modules(host_editor_download(release)?, &options.modules)?;
+    check_eula_acceptance(&modules, options.accept_eula)?;
+
+    let editor_dir = if is_installed {
+        release.version.editor_dir()?
+    } else {
+        let editor_dir = install_editor(release, &Installations::install_root())?;
+        MessageType::print_line(
+            "Installed",
+            format!(
+                "Unity {} in `{}`",
+                release.version.bold(),
+                editor_dir.normalized_display()
+            ),
+            MessageType::Ok,
+        );
+        editor_dir
+    };
+
+    for module in &modules {
+        if install_module(module, &editor_dir)? {
+            MessageType::print_line("Installed", module.name, MessageType::Ok);
+        } else {
+            MessageType::print_line(
+                "Skipped",
+                format!("{} is already installed", module.name),
+                MessageType::Info,
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns an error listing the EULAs of the modules if they have not been accepted.
+fn check_eula_acceptance(modules: &[Module<'_>], accept_eula: bool) -> anyhow::Result<()> {
+    let eulas = modules.iter().flat_map(|m| m.eula).collect::<Vec<_>>();
+    if eulas.is_empty() {
+        return Ok(());
+    }
+
+    if accept_eula {
+        for eula in eulas {
+            MessageType::print_line("Accepted", &eula.label, MessageType::Info);
+        }
+        return Ok(());
+    }
+
+    let list = eulas
+        .iter()
+        .map(|eula| format!("- {}: {}", eula.label, eula.url))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    Err(anyhow!(
+        "The selected modules require accepting the following license agreements, use `--accept-eula` to accept them:\n{list}"
+    ))
+}
+
+/// Opens Unity Hub with the deep link to install the editor.
+fn open_unity_hub(release: &ReleaseData) -> anyhow::Result<()> {
     println!(
         "Opening Unity Hub with deep link {l} to install version {v}",
         l = release.unity_hub_deep_link.paint(LINK),
@@ -43,6 +171,10 @@ pub fn install_version(release: &ReleaseData) -> anyhow::Result<()> {
         std::process::Command::new("open")
             .args([deep_link])
             .status()?
+    } else if cfg!(target_os = "linux") {
+        std::process::Command::new("xdg-open")
+            .args([deep_link])
+            .status()?
     } else {
         anyhow::bail!("Unsupported OS for Unity Hub deep linking");
     };
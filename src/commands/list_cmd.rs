@@ -55,7 +55,7 @@ pub fn list_versions(
 /// ── 2022.3.57f1 - https://unity.com/releases/editor/whats-new/2022.3.57#notes
 /// ┬─ 6000.0.32f1 - https://unity.com/releases/editor/whats-new/6000.0.32#notes
 /// ├─ 6000.0.35f1 - https://unity.com/releases/editor/whats-new/6000.0.35#notes
-/// └─ 6000.0.36f1 * https://unity.com/releases/editor/whats-new/6000.0.36#notes
+/// └─ 6000.0.36f1 * https://unity.com/releases/editor/whats-new/6000.0.36#notes + android, ios
 /// ```
 fn display_installed_versions(installed: &Installations, mode: UpdatePolicy) -> anyhow::Result<()> {
     let releases = if mode == UpdatePolicy::Incremental {
@@ -68,27 +68,58 @@ fn display_installed_versions(installed: &Installations, mode: UpdatePolicy) ->
     report.header(
         format_args!(
             "Unity versions in: {di} {sv}",
-            di = &installed
-                .install_dir
-                .normalized_display()
-                .when(report.is_markdown())
-                .md_code(),
+            di = format_install_roots(installed, &report),
             sv = format_suggested_version(&releases),
         ),
         HeaderLevel::H1,
     );
 
     if releases.is_empty() {
-        display_basic_list(&installed.versions, &report);
+        display_basic_list(installed, &report);
     } else {
-        display_list_with_release_dates(&installed.versions, &releases, &report);
+        display_list_with_release_dates(installed, &releases, &report);
     }
 
     Ok(())
 }
 
-fn display_basic_list(installed: &SortedVersions, report: &Report) {
-    let version_groups = group_versions_by_minor(installed);
+/// Returns the root directories of the installations as a comma separated list.
+fn format_install_roots(installed: &Installations, report: &Report) -> String {
+    installed
+        .roots
+        .iter()
+        .map(|root| {
+            root.normalized_display()
+                .when(report.is_markdown())
+                .md_code()
+                .to_string()
+        })
+        .join(", ")
+}
+
+/// Returns the installed modules of the version and, if the installations are spread over
+/// multiple roots, the root directory the version was found in.
+fn format_installation_details(installed: &Installations, version: Version) -> String {
+    let Some(location) = installed.location(version) else {
+        return String::default();
+    };
+
+    let mut details = String::default();
+
+    let modules = location.installed_modules();
+    if !modules.is_empty() {
+        details.push_str(&format!(" + {}", modules.join(", ")));
+    }
+
+    if installed.has_multiple_roots() {
+        details.push_str(&format!(" [{}]", location.root.normalized_display()));
+    }
+
+    details
+}
+
+fn display_basic_list(installed: &Installations, report: &Report) {
+    let version_groups = group_versions_by_minor(&installed.versions);
     let max_len = find_max_version_length(&version_groups);
 
     for group in version_groups.iter() {
@@ -99,21 +130,22 @@ fn display_basic_list(installed: &SortedVersions, report: &Report) {
             );
 
             report.paragraph(format_args!(
-                "{line_marker} {vs:<max_len$} - {rn}",
+                "{line_marker} {vs:<max_len$} - {rn}{rs}",
                 vs = info.version.to_interned_str(),
-                rn = release_notes_url(info.version).paint(LINK)
+                rn = release_notes_url(info.version).paint(LINK),
+                rs = format_installation_details(installed, info.version),
             ));
         }
     }
 }
 
 fn display_list_with_release_dates(
-    installed: &SortedVersions,
+    installed: &Installations,
     releases: &Releases,
     report: &Report,
 ) {
     const CODE_BLOCK: &str = "```";
-    let version_groups = group_versions_by_minor(installed);
+    let version_groups = group_versions_by_minor(&installed.versions);
     let max_len = find_max_version_length(&version_groups);
 
     report.when(report.is_markdown()).paragraph(CODE_BLOCK);
@@ -128,7 +160,7 @@ fn display_list_with_release_dates(
             let has_error = error_label.is_some();
 
             report.paragraph(format_args!(
-                "{lm}{sp} {ri}",
+                "{lm}{sp} {ri}{rs}",
                 lm = slim_branch_marker(
                     info.version == group.first().version,
                     info.version == group.last().version,
@@ -153,7 +185,8 @@ fn display_list_with_release_dates(
                     description = format_release_description(info, release),
                 )
                 .bold()
-                .whenever(Condition::cached(is_suggested))
+                .whenever(Condition::cached(is_suggested)),
+                rs = format_installation_details(installed, info.version),
             ));
         }
     }
@@ -178,11 +211,7 @@ fn display_updates(installed: &Installations, mode: UpdatePolicy) -> anyhow::Res
     report.header(
         format_args!(
             "Updates for Unity versions in: {di} {sv}",
-            di = installed
-                .install_dir
-                .normalized_display()
-                .when(report.is_markdown())
-                .md_code(),
+            di = format_install_roots(installed, &report),
             sv = format_suggested_version(releases.as_ref())
         ),
         HeaderLevel::H1,
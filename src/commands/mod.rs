@@ -5,36 +5,64 @@ use chrono::TimeDelta;
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::process::Command;
+use std::thread;
+use std::time::Duration;
 use yansi::Paint;
 
 pub use crate::commands::add_cmd::add_to_project;
-pub use crate::commands::build_cmd::build_project;
+pub use crate::commands::archive_cmd::archive_build_output;
+pub use crate::commands::build_cmd::{build_project, list_build_presets};
+pub use crate::commands::clean_cmd::clean_project;
+pub use crate::commands::config_cmd::show_config;
+pub use crate::commands::editor_cmd::editor_command;
+pub use crate::commands::gc_cmd::collect_unused_versions;
 pub use crate::commands::info_cmd::project_info;
-pub use crate::commands::install_cmd::install_latest_matching;
+pub use crate::commands::install_cmd::{InstallMethod, InstallOptions, install_latest_matching};
 pub use crate::commands::list_cmd::list_versions;
 pub use crate::commands::new_cmd::new_project;
 pub use crate::commands::open_cmd::open_project;
 pub use crate::commands::run_cmd::run_unity;
+pub use crate::commands::uninstall_cmd::uninstall_version;
 pub use crate::commands::updates_cmd::find_project_updates;
+pub use crate::commands::version_cmd::project_version;
 use crate::style_definitions::{ERROR, LINK, UNSTYLED};
+use crate::unity::project::{Manifest, Packages};
 use crate::unity::release_api::{UpdatePolicy, fetch_latest_releases};
 use crate::unity::release_api_data::LabelElement;
-use crate::unity::{ProjectPath, Version};
+use crate::unity::transient_failures::{UpmLog, find_transient_failure};
+use crate::unity::{CommandError, ProjectPath, Version};
+use crate::utils::format_duration;
 use crate::utils::report::{HeaderLevel, Report, WrapMode};
+use crate::utils::status_line::MessageType;
 
 mod add_cmd;
+mod archive_cmd;
 mod build_cmd;
+mod build_fingerprint;
+mod build_manifest;
+mod builder_script;
+mod clean_cmd;
+mod config_cmd;
+mod editor_cmd;
+mod editor_ipc;
+mod gc_cmd;
 mod info_cmd;
 mod install_cmd;
 mod list_cmd;
 mod new_cmd;
 mod open_cmd;
 mod run_cmd;
+mod uninstall_cmd;
 mod updates_cmd;
+mod version_cmd;
 
 pub mod test_cmd;
 
 pub const PERSISTENT_BUILD_SCRIPT_ROOT: &str = "Assets/Plugins/Ucom/Editor";
+/// The prefix of the directories in which the build script is injected for a single build.
+pub const AUTO_BUILD_SCRIPT_ROOT: &str = "Assets/Ucom";
+/// Disables the test command in the builder script of projects without the Test Framework package.
+pub const NO_TEST_FRAMEWORK_DEFINE: &str = "#define UCOM_NO_TEST_FRAMEWORK";
 pub const INDENT: &str = "  ";
 pub const MARK_UP_TO_DATE: char = '✓';
 pub const MARK_UPDATES_AVAILABLE: char = '!';
@@ -46,6 +74,9 @@ pub const MARK_UNAVAILABLE: char = '✗';
 pub const MARK_ERROR: char = '‼';
 pub const MARK_SUGGESTED: char = '*';
 
+/// The delay before retrying a transient failure, doubled for every following retry.
+const RETRY_DELAY: Duration = Duration::from_secs(10);
+
 trait TimeDeltaExt {
     fn as_seconds(&self) -> f64;
 }
@@ -65,7 +96,7 @@ fn add_file_to_project(
 ) -> anyhow::Result<()> {
     let template_data = template_file.as_asset();
     let file_path = destination_dir.as_ref().join(template_data.filename);
-    let content = template_data.load_content()?;
+    let content = template_content_for(project_root.as_ref(), template_file)?;
 
     create_file(project_root.as_ref().join(&file_path), &content)
         .inspect(|()| {
@@ -82,6 +113,32 @@ fn add_file_to_project(
         })
 }
 
+/// Returns the content of the template as it is added to the project.
+fn template_content_for(
+    project_root: &Path,
+    template_file: UnityTemplateFile,
+) -> anyhow::Result<String> {
+    let content = template_file.as_asset().load_content()?;
+
+    if template_file == UnityTemplateFile::Builder && !has_test_framework(project_root) {
+        // The test command of the builder script doesn't compile without the Test Framework.
+        return Ok(format!("{NO_TEST_FRAMEWORK_DEFINE}\n{content}"));
+    }
+    Ok(content.into_owned())
+}
+
+/// Returns true if the project depends on the Test Framework package, or if it cannot be determined.
+/// Other packages can depend on it too, so the lock file is also checked.
+fn has_test_framework(project_root: &Path) -> bool {
+    const TEST_FRAMEWORK: &str = "com.unity.test-framework";
+
+    Manifest::from_project(project_root).map_or(true, |manifest| {
+        manifest.dependencies.contains_key(TEST_FRAMEWORK)
+            || Packages::from_lock_file(project_root)
+                .is_ok_and(|packages| packages.dependencies.contains_key(TEST_FRAMEWORK))
+    })
+}
+
 fn create_file(file_path: impl AsRef<Path>, content: &str) -> anyhow::Result<()> {
     let file_path = file_path.as_ref();
     let parent_dir = file_path
@@ -157,13 +214,52 @@ pub fn execute_unity_command(cmd: Command, wait: bool, quiet: bool) -> anyhow::R
     }
 
     if wait {
-        crate::unity::wait_with_stdout(cmd)?;
+        crate::unity::wait_with_stdout(cmd, &crate::unity::Timeouts::default())?;
     } else {
         crate::unity::spawn_and_forget(cmd)?;
     }
     Ok(())
 }
 
+/// Runs a Unity command and reruns it, up to `retries` times, when the logs show that it failed
+/// because of a transient failure. `failed` tells if an attempt failed. Returns the result of the
+/// last attempt.
+pub fn run_with_retries(
+    retries: u32,
+    log_file: &Path,
+    mut run: impl FnMut() -> Result<(), CommandError>,
+    failed: impl Fn(&Result<(), CommandError>) -> bool,
+) -> Result<(), CommandError> {
+    let mut retry = 0;
+    loop {
+        let upm_log = UpmLog::mark();
+        let result = run();
+
+        // A timeout is a limit set by the user, not a transient failure.
+        if retry == retries || !failed(&result) || result.as_ref().is_err_and(|e| e.timed_out) {
+            return result;
+        }
+
+        let Some(reason) = find_transient_failure(log_file, &upm_log) else {
+            return result;
+        };
+
+        let delay = RETRY_DELAY * 2u32.pow(retry.min(5));
+        retry += 1;
+        MessageType::print_line(
+            "Retrying",
+            format!(
+                "{reason}; attempt {} of {} in {}",
+                retry + 1,
+                retries + 1,
+                format_duration(delay)
+            ),
+            MessageType::Warning,
+        );
+        thread::sleep(delay);
+    }
+}
+
 /// Unified project setup for commands
 pub struct ProjectSetup {
     pub project: ProjectPath,
@@ -34,7 +34,13 @@ pub fn new_project(arguments: NewArguments) -> anyhow::Result<()> {
         ));
     }
 
-    let version = Installations::latest_installed_version(Some(&arguments.version_pattern))?;
+    let version_pattern = arguments.version_pattern.as_deref().ok_or_else(|| {
+        anyhow!(
+            "No Unity version specified. Pass it with `--unity` or set `unity` in the `[new]` section of the configuration."
+        )
+    })?;
+
+    let version = Installations::latest_installed_version(Some(version_pattern))?;
     let editor_exe = version.editor_executable_path()?;
 
     let mut builder = UnityCommandBuilder::new(editor_exe)
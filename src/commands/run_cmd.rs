@@ -1,16 +1,31 @@
+use std::path::PathBuf;
+
+use anyhow::anyhow;
+
 use crate::cli_run::RunArguments;
 use crate::commands::{UnityCommandBuilder, execute_unity_command};
-use crate::unity::build_command_line;
 use crate::unity::installations::Installations;
+use crate::unity::{Timeouts, build_command_line, wait_with_stdout};
 
 /// Runs the Unity Editor with the given arguments.
 pub fn run_unity(arguments: RunArguments) -> anyhow::Result<()> {
     let unity_version = Installations::latest_installed_version(Some(&arguments.version_pattern))?;
     let editor_exe = unity_version.editor_executable_path()?;
 
-    let run_command = UnityCommandBuilder::new(editor_exe)
-        .add_args(arguments.args.unwrap_or_default())
-        .build();
+    let args = arguments.args.unwrap_or_default();
+    let timeouts = Timeouts {
+        total: arguments.timeout,
+        idle: arguments.idle_timeout,
+        log_file: log_file_arg(&args),
+    };
+
+    if timeouts.idle.is_some() && timeouts.log_file.is_none() {
+        return Err(anyhow!(
+            "--idle-timeout requires the '-logFile <FILE>' Unity argument to watch for output"
+        ));
+    }
+
+    let run_command = UnityCommandBuilder::new(editor_exe).add_args(args).build();
 
     if arguments.dry_run {
         println!("{}", build_command_line(&run_command));
@@ -21,5 +36,23 @@ pub fn run_unity(arguments: RunArguments) -> anyhow::Result<()> {
         println!("Run Unity {unity_version}");
     }
 
-    execute_unity_command(run_command, arguments.wait, arguments.quiet)
+    if timeouts.total.is_none() && timeouts.idle.is_none() {
+        return execute_unity_command(run_command, arguments.wait, arguments.quiet);
+    }
+
+    // A timeout can only be enforced while waiting for Unity to exit.
+    if !arguments.quiet {
+        println!("Running: {}", build_command_line(&run_command));
+    }
+    wait_with_stdout(run_command, &timeouts)?;
+    Ok(())
+}
+
+/// Returns the value of the `-logFile` argument, unless it is `-` (log to stdout).
+fn log_file_arg(args: &[String]) -> Option<PathBuf> {
+    args.iter()
+        .skip_while(|arg| !arg.eq_ignore_ascii_case("-logFile"))
+        .nth(1)
+        .filter(|path| !path.starts_with('-'))
+        .map(PathBuf::from)
 }
@@ -6,17 +6,18 @@ use chrono::prelude::*;
 use yansi::Paint;
 
 use crate::cli_test::{ShowResults, TestArguments};
-use crate::commands::{ProjectSetup, TimeDeltaExt, UnityCommandBuilder};
+use crate::commands::editor_ipc::{EditorChannel, EditorCommand, EditorResult, TestCommand};
+use crate::commands::{ProjectSetup, TimeDeltaExt, UnityCommandBuilder, run_with_retries};
 use crate::nunit::{TestCase, TestResult, TestRun};
 use crate::style_definitions::{ERROR, UNSTYLED};
-use crate::unity::{ProjectPath, build_command_line, wait_with_stdout};
+use crate::unity::diagnostics::{Severity, parse_log_file, report_log_errors};
+use crate::unity::{ProjectPath, Timeouts, build_command_line, wait_with_stdout};
 use crate::utils::path_ext::PlatformConsistentPathExt;
 use crate::utils::status_line::{MessageType, StatusLine};
 
 pub fn run_tests(arguments: &TestArguments) -> anyhow::Result<()> {
     let start_time = Utc::now();
     let setup = ProjectSetup::new(&arguments.project_dir)?;
-    let editor_exe = setup.editor_executable()?;
     setup.project.ensure_assets_directory_exists()?;
 
     let test_results = format!(
@@ -26,9 +27,20 @@ pub fn run_tests(arguments: &TestArguments) -> anyhow::Result<()> {
     );
 
     let output_path = setup.project.join(test_results);
-    let test_command = arguments.build_cmd(&setup.project, &editor_exe, &output_path);
 
+    // Run the tests in the editor if it has the project open.
+    if !arguments.dry_run && try_editor_tests(arguments, &setup.project, &output_path)? {
+        return report_results(arguments, &start_time, &setup.project, &output_path);
+    }
+
+    let editor_exe = setup.editor_executable()?;
+    let log_path = setup
+        .project
+        .join("Logs")
+        .join(format!("Test-{}.log", arguments.platform));
     if arguments.dry_run {
+        let test_command =
+            arguments.build_cmd(&setup.project, &editor_exe, &output_path, &log_path);
         println!("{}", build_command_line(&test_command));
         return Ok(());
     }
@@ -46,17 +58,51 @@ pub fn run_tests(arguments: &TestArguments) -> anyhow::Result<()> {
                 ),
             )
         };
-        wait_with_stdout(test_command)
+        let timeouts = Timeouts {
+            total: arguments.timeout,
+            idle: arguments.idle_timeout,
+            log_file: Some(log_path.clone()),
+        };
+        run_with_retries(
+            arguments.retries,
+            &log_path,
+            || {
+                wait_with_stdout(
+                    arguments.build_cmd(&setup.project, &editor_exe, &output_path, &log_path),
+                    &timeouts,
+                )
+            },
+            // Unity doesn't always return an error when the tests cannot run. Exit code 2 means the
+            // tests ran and some failed, running them again won't help.
+            |result| match result {
+                Err(e) => e.exit_code != 2,
+                Ok(_) => !output_path.exists(),
+            },
+        )
+    };
+
+    let tests_result = match tests_result {
+        Err(e) if e.timed_out => return Err(e.into()),
+        result => result,
     };
 
     if let Err(e) = &tests_result {
-        // If the error was not caused by the command exiting with code 2 (tests failed), return it.
+        // If the error was not caused by the command exiting with code 2 (tests failed), return it
+        // with the errors from the log, e.g. compiler errors.
         if e.exit_code != 2 {
-            return Err(anyhow!("{e}"));
+            return Err(report_log_errors(&log_path, &setup.project).context(e.to_string()));
         }
     }
 
     if !output_path.exists() {
+        // Unity doesn't return an error when the tests cannot run, e.g. because of compiler
+        // errors or when the project is already open.
+        if parse_log_file(&log_path).is_ok_and(|d| d.iter().any(|d| d.severity == Severity::Error))
+        {
+            return Err(report_log_errors(&log_path, &setup.project)
+                .context("Unable to run tests because of errors"));
+        }
+
         // Stupid workaround for Unity not returning an error when project is already open.
         return Err(anyhow!(
             "Unable to run tests, is another Unity instance running with this same project open?"
@@ -80,6 +126,100 @@ pub fn run_tests(arguments: &TestArguments) -> anyhow::Result<()> {
     }
 }
 
+/// Runs the tests in the Unity editor that has the project open.
+///
+/// Returns `Ok(false)` if no editor has the project open, the caller should run the tests in
+/// batch mode.
+fn try_editor_tests(
+    arguments: &TestArguments,
+    project: &ProjectPath,
+    output_path: &Path,
+) -> anyhow::Result<bool> {
+    let Some(channel) = EditorChannel::open(project)? else {
+        return Ok(false);
+    };
+
+    let _status = if arguments.quiet {
+        StatusLine::new_silent()
+    } else {
+        StatusLine::new(
+            "Running",
+            format!(
+                "{} tests in the editor for project in {}",
+                &arguments.platform,
+                project.normalized_display()
+            ),
+        )
+    };
+
+    let command = EditorCommand::Test(TestCommand {
+        platform: arguments.platform.as_ref().to_string(),
+        categories: split_list(arguments.categories.as_deref()),
+        tests: split_list(arguments.tests.as_deref()),
+        assemblies: split_list(arguments.assemblies.as_deref()),
+        results_path: output_path.to_string_lossy().to_string(),
+    });
+
+    let result: EditorResult = channel.send(&command)?.wait_for_result(arguments.timeout)?;
+
+    if result.status != "success" {
+        return Err(anyhow!(
+            "{} ({})",
+            result.message,
+            result.error_code.as_deref().unwrap_or("UNKNOWN_ERROR")
+        ));
+    }
+
+    if !output_path.exists() {
+        return Err(anyhow!(
+            "The Unity editor did not write the test results to {}",
+            output_path.normalized_display()
+        ));
+    }
+    Ok(true)
+}
+
+/// Prints the results of a test run in the editor and exits with code 2 when tests failed, like
+/// Unity does in batch mode.
+fn report_results(
+    arguments: &TestArguments,
+    start_time: &DateTime<Utc>,
+    project: &ProjectPath,
+    output_path: &Path,
+) -> anyhow::Result<()> {
+    let test_run = TestRun::from_file(output_path)?;
+    let failed = test_run.stats.failed > 0;
+
+    if !arguments.quiet {
+        let status = if failed {
+            MessageType::Error
+        } else {
+            MessageType::Ok
+        };
+
+        print_results(arguments, start_time, project, output_path, status)?;
+    }
+
+    if failed {
+        exit(2);
+    } else {
+        Ok(())
+    }
+}
+
+/// Splits a semicolon-separated list of filters, as accepted by Unity on the command line.
+fn split_list(list: Option<&str>) -> Vec<String> {
+    list.map(|list| {
+        list.trim_matches('"')
+            .split(';')
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .map(ToString::to_string)
+            .collect()
+    })
+    .unwrap_or_default()
+}
+
 fn print_results(
     arguments: &TestArguments,
     start_time: &DateTime<Utc>,
@@ -137,10 +277,17 @@ fn print_results(
 }
 
 impl TestArguments {
-    fn build_cmd(&self, project: &ProjectPath, editor_exe: &Path, output_dir: &Path) -> Command {
+    fn build_cmd(
+        &self,
+        project: &ProjectPath,
+        editor_exe: &Path,
+        output_dir: &Path,
+        log_file: &Path,
+    ) -> Command {
         // Build the command using the builder pattern.
         let mut builder = UnityCommandBuilder::new(editor_exe.to_path_buf())
             .with_project_path(project.to_path_buf())
+            .with_log_file(log_file)
             .add_arg("-runTests")
             .add_arg("-testPlatform")
             .add_arg(self.platform.as_ref())
@@ -0,0 +1,70 @@
+use std::fs;
+
+use anyhow::{Context, anyhow};
+use itertools::Itertools;
+use yansi::Paint;
+
+use crate::unity::installations::{EditorLocation, Installations};
+use crate::unity::{Version, is_editor_installation_running};
+use crate::utils::path_ext::PlatformConsistentPathExt;
+use crate::utils::status_line::{MessageType, StatusLine};
+use crate::utils::{confirm, dir_size, format_size};
+
+/// Uninstalls the installed version that matches the given prefix.
+pub fn uninstall_version(version_prefix: &str, yes: bool) -> anyhow::Result<()> {
+    let installed = Installations::find_installations(Some(version_prefix))?;
+    if installed.versions.len() > 1 {
+        return Err(anyhow!(
+            "Multiple installed versions match `{version_prefix}`: {}",
+            installed.versions.iter().join(", ")
+        ));
+    }
+
+    let version = *installed.versions.first();
+    let location = installed
+        .location(version)
+        .ok_or_else(|| anyhow!("Unity version is not installed: {version}"))?;
+
+    let size = {
+        let _status = StatusLine::new("Calculating", format!("size of Unity {version}"));
+        dir_size(&location.dir)
+    };
+
+    if !yes
+        && !confirm(format!(
+            "Remove Unity {} ({}) from `{}`?",
+            version.bold(),
+            format_size(size),
+            location.dir.normalized_display()
+        ))?
+    {
+        return Ok(());
+    }
+
+    remove_installation(version, location)?;
+    MessageType::print_line(
+        "Removed",
+        format!("Unity {version}, freed {}", format_size(size)),
+        MessageType::Ok,
+    );
+    Ok(())
+}
+
+/// Removes the installation directory of the editor.
+pub fn remove_installation(version: Version, location: &EditorLocation) -> anyhow::Result<()> {
+    if is_editor_installation_running(&location.dir) {
+        return Err(anyhow!("Cannot remove Unity {version} while it is running"));
+    }
+
+    let _status = StatusLine::new("Removing", format!("Unity {version}"));
+    let result = fs::remove_dir_all(&location.dir).with_context(|| {
+        format!(
+            "Cannot remove Unity {version} from `{}`",
+            location.dir.normalized_display()
+        )
+    });
+
+    // Even a failed removal may have removed part of the installation.
+    Installations::refresh();
+    result
+}
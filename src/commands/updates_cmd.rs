@@ -2,7 +2,7 @@ use anyhow::anyhow;
 use std::path::Path;
 use yansi::Paint;
 
-use crate::commands::install_cmd::install_version;
+use crate::commands::install_cmd::{InstallOptions, install_version};
 use crate::commands::*;
 use crate::style_definitions::{
     ERROR, HAS_UPDATE, IS_UPDATE, LINK, OK, UNSTYLED, UP_TO_DATE, WARNING,
@@ -97,7 +97,7 @@ fn handle_newer_release_installation(
             (false, true) => {
                 // There is a newer version available, and the user wants to install it.
                 report.blank_line();
-                install_version(newer_release)?;
+                install_version(newer_release, &InstallOptions::unity_hub())?;
             }
             (false, false) => {
                 // There is a newer version available, but the user has not requested installation.
@@ -0,0 +1,226 @@
+use std::path::Path;
+use std::process::Command;
+
+use anyhow::{Context, anyhow};
+use yansi::Paint;
+
+use crate::cli::{VersionAction, VersionPart};
+use crate::unity::ProjectPath;
+use crate::unity::project_settings_file::ProjectSettingsFile;
+use crate::utils::path_ext::PlatformConsistentPathExt;
+use crate::utils::status_line::MessageType;
+
+const BUNDLE_VERSION: &str = "bundleVersion";
+const ANDROID_VERSION_CODE: &str = "AndroidBundleVersionCode";
+const BUILD_NUMBER: &str = "buildNumber";
+const IOS_PLATFORM: &str = "iPhone";
+
+/// Runs the version command.
+pub fn project_version(action: &VersionAction) -> anyhow::Result<()> {
+    match action {
+        VersionAction::Show { project_dir } => show_version(project_dir),
+        VersionAction::Set {
+            version,
+            from_git,
+            build_number,
+            dry_run,
+            project_dir,
+        } => {
+            let project = ProjectPath::try_from(project_dir)?;
+            let version = match version {
+                Some(version) => version.clone(),
+                None if *from_git => latest_git_tag_version(&project)?,
+                None => return Err(anyhow!("No version specified")),
+            };
+            parse_version(&version)?;
+            let version = version.trim().trim_start_matches(['v', 'V']).to_string();
+            update_version(&project, |_| Ok(version), *build_number, *dry_run)
+        }
+        VersionAction::Bump {
+            part,
+            build_number,
+            dry_run,
+            project_dir,
+        } => {
+            let project = ProjectPath::try_from(project_dir)?;
+            update_version(
+                &project,
+                |current| bump_version(current, *part),
+                *build_number,
+                *dry_run,
+            )
+        }
+    }
+}
+
+fn show_version(project_dir: &Path) -> anyhow::Result<()> {
+    let project = ProjectPath::try_from(project_dir)?;
+    let settings = ProjectSettingsFile::open(&project)?;
+
+    println!("Version info for: {}", project.normalized_display().bold());
+    print_setting("Version", settings.value(BUNDLE_VERSION));
+    print_setting("Android", settings.value(ANDROID_VERSION_CODE));
+    print_setting("iOS", settings.platform_value(BUILD_NUMBER, IOS_PLATFORM));
+    Ok(())
+}
+
+fn print_setting(label: &str, value: Option<&str>) {
+    match value {
+        Some(value) => MessageType::print_line(label, value, MessageType::Info),
+        None => MessageType::print_line(label, "not set", MessageType::Warning),
+    }
+}
+
+/// Sets the bundle version to the result of `new_version` and updates the Android version code and
+/// the iOS build number. The build numbers are incremented, unless `build_number` is given.
+fn update_version(
+    project: &ProjectPath,
+    new_version: impl FnOnce(&str) -> anyhow::Result<String>,
+    build_number: Option<u32>,
+    dry_run: bool,
+) -> anyhow::Result<()> {
+    let mut settings = ProjectSettingsFile::open(project)?;
+
+    let current_version = settings
+        .value(BUNDLE_VERSION)
+        .unwrap_or_default()
+        .to_string();
+    let version = new_version(&current_version)?;
+
+    let current_code = settings
+        .value(ANDROID_VERSION_CODE)
+        .unwrap_or_default()
+        .to_string();
+    let code = match build_number {
+        Some(number) => number.to_string(),
+        None => increment_build_number(&current_code)
+            .with_context(|| format!("Invalid `{ANDROID_VERSION_CODE}`: `{current_code}`"))?,
+    };
+
+    // Projects that never targeted iOS have no build number, it is then started at 1.
+    let current_ios_number = settings
+        .platform_value(BUILD_NUMBER, IOS_PLATFORM)
+        .unwrap_or("0")
+        .to_string();
+    let ios_number = match build_number {
+        Some(number) => number.to_string(),
+        None => increment_build_number(&current_ios_number)
+            .with_context(|| format!("Invalid iOS `{BUILD_NUMBER}`: `{current_ios_number}`"))?,
+    };
+
+    settings.set_value(BUNDLE_VERSION, &version)?;
+    settings.set_value(ANDROID_VERSION_CODE, &code)?;
+    settings.set_platform_value(BUILD_NUMBER, IOS_PLATFORM, &ios_number)?;
+
+    print_change("Version", &current_version, &version);
+    print_change("Android", &current_code, &code);
+    print_change("iOS", &current_ios_number, &ios_number);
+
+    if dry_run {
+        return Ok(());
+    }
+
+    settings.save()?;
+    MessageType::print_line(
+        "Updated",
+        settings.path().normalized_display().to_string(),
+        MessageType::Ok,
+    );
+    Ok(())
+}
+
+fn print_change(label: &str, from: &str, to: &str) {
+    MessageType::print_line(label, format!("{from} -> {}", to.bold()), MessageType::Info);
+}
+
+/// Parses a `major.minor.patch` version, missing parts are 0. A `v` prefix is allowed.
+fn parse_version(version: &str) -> anyhow::Result<[u32; 3]> {
+    let invalid = || anyhow!("Invalid version `{version}`, expected `major.minor.patch`");
+
+    let trimmed = version.trim().trim_start_matches(['v', 'V']);
+    let parts = trimmed.split('.').collect::<Vec<_>>();
+    if parts.is_empty() || parts.len() > 3 {
+        return Err(invalid());
+    }
+
+    let mut numbers = [0; 3];
+    for (number, part) in numbers.iter_mut().zip(parts) {
+        *number = part.parse().map_err(|_| invalid())?;
+    }
+    Ok(numbers)
+}
+
+/// Returns the version with the given part incremented and the parts after it reset to 0.
+fn bump_version(version: &str, part: VersionPart) -> anyhow::Result<String> {
+    let [major, minor, patch] = parse_version(version)?;
+    let [major, minor, patch] = match part {
+        VersionPart::Major => [major + 1, 0, 0],
+        VersionPart::Minor => [major, minor + 1, 0],
+        VersionPart::Patch => [major, minor, patch + 1],
+    };
+    Ok(format!("{major}.{minor}.{patch}"))
+}
+
+/// Increments the last part of a build number, e.g. `41` becomes `42` and `1.0.7` becomes `1.0.8`.
+fn increment_build_number(number: &str) -> anyhow::Result<String> {
+    let (prefix, last) = number.rsplit_once('.').unwrap_or(("", number));
+    let last = last
+        .trim()
+        .parse::<u32>()
+        .map_err(|_| anyhow!("Build number is not numeric"))?;
+
+    if prefix.is_empty() {
+        Ok((last + 1).to_string())
+    } else {
+        Ok(format!("{prefix}.{}", last + 1))
+    }
+}
+
+/// Returns the version of the latest tag reachable from the current commit, e.g. `v1.2.0`.
+fn latest_git_tag_version(project: &Path) -> anyhow::Result<String> {
+    let output = Command::new("git")
+        .current_dir(project)
+        .args(["describe", "--tags", "--abbrev=0"])
+        .output()
+        .context("Could not run Git. Make sure Git is available.")?;
+
+    if !output.status.success() {
+        return Err(anyhow!(
+            "Could not find a git tag: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    let tag = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    let [major, minor, patch] = parse_version(&tag)
+        .with_context(|| format!("The latest git tag `{tag}` is not a version"))?;
+    Ok(format!("{major}.{minor}.{patch}"))
+}
+
+//
+// Tests
+//
+
+#[cfg(test)]
+mod version_cmd_tests {
+    use super::*;
+
+    #[test]
+    fn test_bump_version() {
+        assert_eq!(bump_version("1.2.3", VersionPart::Major).unwrap(), "2.0.0");
+        assert_eq!(bump_version("1.2.3", VersionPart::Minor).unwrap(), "1.3.0");
+        assert_eq!(bump_version("1.2.3", VersionPart::Patch).unwrap(), "1.2.4");
+        assert_eq!(bump_version("0.1", VersionPart::Patch).unwrap(), "0.1.1");
+        assert_eq!(bump_version("v2", VersionPart::Minor).unwrap(), "2.1.0");
+        assert!(bump_version("1.2.3-beta", VersionPart::Patch).is_err());
+        assert!(bump_version("1.2.3.4", VersionPart::Patch).is_err());
+    }
+
+    #[test]
+    fn test_increment_build_number() {
+        assert_eq!(increment_build_number("41").unwrap(), "42");
+        assert_eq!(increment_build_number("1.0.7").unwrap(), "1.0.8");
+        assert!(increment_build_number("").is_err());
+        assert!(increment_build_number("abc").is_err());
+    }
+}
@@ -0,0 +1,790 @@
+use std::env;
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use anyhow::{Context, anyhow};
+use clap::parser::ValueSource;
+use clap::{ArgMatches, ValueEnum};
+use itertools::Itertools;
+
+use crate::cli::{ENV_BUILD_TARGET, OpenArguments};
+use crate::cli_build::{
+    ArchiveFormat, BuildArguments, BuildMode, BuildOpenTarget, BuildOptions, BuildOutputType,
+    InjectAction, OpenTarget, TargetOrDirectory,
+};
+use crate::cli_new::NewArguments;
+use crate::cli_test::{ShowResults, TestArguments};
+use crate::utils::path_ext::PlatformConsistentPathExt;
+use crate::utils::{format_duration, parse_duration};
+
+/// Name of the configuration file in the project directory.
+pub const PROJECT_CONFIG_FILE: &str = "ucom.toml";
+
+/// Returns the path of the user configuration file.
+pub fn user_config_path() -> Option<PathBuf> {
+    dirs::home_dir().map(|home| home.join(".config/ucom/config.toml"))
+}
+
+//
+// Origin
+//
+
+/// Where the value of a setting comes from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Origin {
+    Default,
+    CommandLine,
+    Environment,
+    Preset,
+    Project,
+    User,
+}
+
+impl Display for Origin {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Self::Default => "default",
+            Self::CommandLine => "command line",
+            Self::Environment => "env",
+            Self::Preset => "preset",
+            Self::Project => "project",
+            Self::User => "user",
+        };
+        f.pad(name)
+    }
+}
+
+//
+// Configuration files
+//
+
+/// A parsed configuration file.
+pub struct ConfigFile {
+    pub path: PathBuf,
+    pub origin: Origin,
+    /// Directory that relative paths in the file are resolved against.
+    base_dir: Option<PathBuf>,
+    table: toml::Table,
+}
+
+impl ConfigFile {
+    /// Reads the configuration file, returns `None` if it does not exist.
+    fn read(path: PathBuf, origin: Origin) -> anyhow::Result<Option<Self>> {
+        if !path.is_file() {
+            return Ok(None);
+        }
+
+        let content = fs::read_to_string(&path)
+            .with_context(|| format!("Cannot read `{}`", path.normalized_display()))?;
+        let table = toml::from_str::<toml::Table>(&content)
+            .with_context(|| format!("Cannot parse `{}`", path.normalized_display()))?;
+
+        let base_dir = match origin {
+            Origin::Project => path.parent().map(Path::to_path_buf),
+            _ => None,
+        };
+
+        Ok(Some(Self {
+            path,
+            origin,
+            base_dir,
+            table,
+        }))
+    }
+
+    /// Returns the value of the key in the given section.
+    fn get(&self, section: &str, key: &str) -> Option<&toml::Value> {
+        self.table.get(section)?.as_table()?.get(key)
+    }
+
+    /// Returns the presets defined in the `[presets.<name>]` sections.
+    fn presets(&self) -> impl Iterator<Item = Preset<'_>> {
+        self.table
+            .get(PRESETS_SECTION)
+            .and_then(toml::Value::as_table)
+            .into_iter()
+            .flatten()
+            .filter_map(|(name, value)| {
+                value.as_table().map(|table| Preset {
+                    name,
+                    table,
+                    file: self,
+                })
+            })
+    }
+}
+
+/// Name of the section that contains the build presets.
+const PRESETS_SECTION: &str = "presets";
+
+/// A named set of build settings, defined in a `[presets.<name>]` section.
+#[derive(Clone, Copy)]
+pub struct Preset<'a> {
+    pub name: &'a str,
+    table: &'a toml::Table,
+    pub file: &'a ConfigFile,
+}
+
+/// The project and user configuration files, in order of precedence.
+pub struct Config {
+    files: Vec<ConfigFile>,
+}
+
+impl Config {
+    /// Loads the project configuration in the given directory and the user configuration.
+    pub fn load(project_dir: &Path) -> anyhow::Result<Self> {
+        let project_file = Some(project_dir.join(PROJECT_CONFIG_FILE));
+        Self::from_files(project_file, user_config_path())
+    }
+
+    /// Loads the given project and user configuration files, skipping files that do not exist.
+    pub fn from_files(
+        project_file: Option<PathBuf>,
+        user_file: Option<PathBuf>,
+    ) -> anyhow::Result<Self> {
+        let files = [(project_file, Origin::Project), (user_file, Origin::User)]
+            .into_iter()
+            .filter_map(|(path, origin)| path.map(|p| ConfigFile::read(p, origin)))
+            .filter_map_ok(|file| file)
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
+        Ok(Self { files })
+    }
+
+    /// Returns the loaded configuration files.
+    pub fn files(&self) -> &[ConfigFile] {
+        &self.files
+    }
+
+    /// Returns the presets by name, a preset in the project configuration hides a user preset
+    /// with the same name.
+    pub fn presets(&self) -> Vec<Preset<'_>> {
+        self.files
+            .iter()
+            .flat_map(ConfigFile::presets)
+            .unique_by(|preset| preset.name)
+            .sorted_by_key(|preset| preset.name)
+            .collect()
+    }
+
+    /// Returns the preset with the given name.
+    pub fn preset(&self, name: &str) -> anyhow::Result<Preset<'_>> {
+        let presets = self.presets();
+        presets
+            .iter()
+            .find(|preset| preset.name == name)
+            .copied()
+            .ok_or_else(|| {
+                if presets.is_empty() {
+                    anyhow!("Unknown preset `{name}`, no presets are configured")
+                } else {
+                    anyhow!(
+                        "Unknown preset `{name}`, available presets: {}",
+                        presets.iter().map(|p| p.name).join(", ")
+                    )
+                }
+            })
+    }
+
+    /// Returns the value with the highest precedence for the key in the given section.
+    fn lookup(&self, section: &str, key: &str) -> Option<(&toml::Value, &ConfigFile)> {
+        self.files
+            .iter()
+            .find_map(|file| file.get(section, key).map(|value| (value, file)))
+    }
+}
+
+//
+// Values
+//
+
+/// A type that can be read from a configuration file.
+pub trait ConfigValue: Sized {
+    /// Converts the configuration value, returns `None` if the value is invalid.
+    fn from_config(value: &toml::Value, base_dir: Option<&Path>) -> Option<Self>;
+
+    /// Returns the value as displayed by `ucom config show`.
+    fn to_config_string(&self) -> String;
+}
+
+impl ConfigValue for bool {
+    fn from_config(value: &toml::Value, _: Option<&Path>) -> Option<Self> {
+        value.as_bool()
+    }
+
+    fn to_config_string(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl ConfigValue for u32 {
+    fn from_config(value: &toml::Value, _: Option<&Path>) -> Option<Self> {
+        value.as_integer().and_then(|v| Self::try_from(v).ok())
+    }
+
+    fn to_config_string(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl ConfigValue for String {
+    fn from_config(value: &toml::Value, _: Option<&Path>) -> Option<Self> {
+        value.as_str().map(ToString::to_string)
+    }
+
+    fn to_config_string(&self) -> String {
+        self.clone()
+    }
+}
+
+impl ConfigValue for PathBuf {
+    fn from_config(value: &toml::Value, base_dir: Option<&Path>) -> Option<Self> {
+        let path = Self::from(value.as_str()?);
+        match base_dir {
+            Some(dir) if path.is_relative() => Some(dir.join(path)),
+            _ => Some(path),
+        }
+    }
+
+    fn to_config_string(&self) -> String {
+        self.normalized_display().to_string()
+    }
+}
+
+/// Accepts a duration string, e.g. `"30m"`.
+impl ConfigValue for Duration {
+    fn from_config(value: &toml::Value, _: Option<&Path>) -> Option<Self> {
+        parse_duration(value.as_str()?).ok()
+    }
+
+    fn to_config_string(&self) -> String {
+        format_duration(*self)
+    }
+}
+
+impl<T: ConfigValue> ConfigValue for Option<T> {
+    fn from_config(value: &toml::Value, base_dir: Option<&Path>) -> Option<Self> {
+        T::from_config(value, base_dir).map(Some)
+    }
+
+    fn to_config_string(&self) -> String {
+        self.as_ref()
+            .map_or_else(|| "-".to_string(), ConfigValue::to_config_string)
+    }
+}
+
+impl<T: ConfigValue> ConfigValue for Vec<T> {
+    /// Accepts an array or a single value.
+    fn from_config(value: &toml::Value, base_dir: Option<&Path>) -> Option<Self> {
+        match value.as_array() {
+            Some(values) => values.iter().map(|v| T::from_config(v, base_dir)).collect(),
+            None => T::from_config(value, base_dir).map(|v| vec![v]),
+        }
+    }
+
+    fn to_config_string(&self) -> String {
+        format!(
+            "[{}]",
+            self.iter().map(ConfigValue::to_config_string).join(", ")
+        )
+    }
+}
+
+/// Implements [`ConfigValue`] for enums that are parsed from their command line name.
+macro_rules! impl_config_value_for_value_enum {
+    ($($t:ty),* $(,)?) => {
+        $(
+            impl ConfigValue for $t {
+                fn from_config(value: &toml::Value, _: Option<&Path>) -> Option<Self> {
+                    <$t as ValueEnum>::from_str(value.as_str()?, true).ok()
+                }
+
+                fn to_config_string(&self) -> String {
+                    self.to_possible_value()
+                        .map(|v| v.get_name().to_string())
+                        .unwrap_or_default()
+                }
+            }
+        )*
+    };
+}
+
+impl_config_value_for_value_enum!(
+    ArchiveFormat,
+    BuildOpenTarget,
+    OpenTarget,
+    BuildOutputType,
+    BuildOptions,
+    BuildMode,
+    InjectAction,
+    ShowResults,
+);
+
+//
+// Resolving
+//
+
+/// A setting with its resolved value and where the value comes from.
+pub struct ResolvedSetting {
+    pub key: &'static str,
+    pub value: String,
+    pub origin: Origin,
+}
+
+/// Applies the configuration to the settings of a command that were not set on the
+/// command line or by an environment variable.
+///
+/// Values of the selected preset are only overridden by the command line.
+pub struct Resolver<'a> {
+    config: &'a Config,
+    section: &'static str,
+    preset: Option<Preset<'a>>,
+    matches: &'a ArgMatches,
+    resolved: Vec<ResolvedSetting>,
+}
+
+impl<'a> Resolver<'a> {
+    pub fn new(
+        config: &'a Config,
+        section: &'static str,
+        preset: Option<Preset<'a>>,
+        matches: &'a ArgMatches,
+    ) -> Self {
+        Self {
+            config,
+            section,
+            preset,
+            matches,
+            resolved: Vec::new(),
+        }
+    }
+
+    /// Sets the field from the configuration `key` if the argument `arg_id` was not set
+    /// on the command line or by an environment variable.
+    pub fn resolve<T: ConfigValue>(
+        &mut self,
+        key: &'static str,
+        arg_id: &str,
+        field: &mut T,
+    ) -> anyhow::Result<()> {
+        let explicit_origin = match self.matches.value_source(arg_id) {
+            Some(ValueSource::CommandLine) => Some(Origin::CommandLine),
+            Some(ValueSource::EnvVariable) => Some(Origin::Environment),
+            _ => None,
+        };
+        self.resolve_with_origin(key, explicit_origin, field)
+    }
+
+    /// Sets the field from the configuration `key` unless it was explicitly set on the command
+    /// line or by an environment variable, as given by `explicit_origin`.
+    pub fn resolve_with_origin<T: ConfigValue>(
+        &mut self,
+        key: &'static str,
+        explicit_origin: Option<Origin>,
+        field: &mut T,
+    ) -> anyhow::Result<()> {
+        let preset_value = self
+            .preset
+            .and_then(|preset| preset.table.get(key).map(|value| (value, preset)));
+
+        let origin = match (explicit_origin, preset_value) {
+            (Some(Origin::CommandLine), _) => Origin::CommandLine,
+            (_, Some((value, preset))) => {
+                *field =
+                    T::from_config(value, preset.file.base_dir.as_deref()).ok_or_else(|| {
+                        anyhow!(
+                            "Invalid value `{value}` for `{key}` in preset `{}` in `{}`",
+                            preset.name,
+                            preset.file.path.normalized_display()
+                        )
+                    })?;
+                Origin::Preset
+            }
+            (Some(origin), _) => origin,
+            (None, None) => match self.config.lookup(self.section, key) {
+                Some((value, file)) => {
+                    *field = T::from_config(value, file.base_dir.as_deref()).ok_or_else(|| {
+                        anyhow!(
+                            "Invalid value `{value}` for `{}.{key}` in `{}`",
+                            self.section,
+                            file.path.normalized_display()
+                        )
+                    })?;
+                    file.origin
+                }
+                None => Origin::Default,
+            },
+        };
+
+        self.resolved.push(ResolvedSetting {
+            key,
+            value: field.to_config_string(),
+            origin,
+        });
+        Ok(())
+    }
+
+    /// Returns the resolved settings, or an error if a configuration file contains unknown keys.
+    pub fn finish(self) -> anyhow::Result<Vec<ResolvedSetting>> {
+        if let Some(preset) = self.preset
+            && let Some(key) = self.find_unknown_key(preset.table)
+        {
+            return Err(anyhow!(
+                "Unknown setting `{key}` in preset `{}` in `{}`",
+                preset.name,
+                preset.file.path.normalized_display()
+            ));
+        }
+
+        for file in &self.config.files {
+            let Some(section) = file.table.get(self.section).and_then(|s| s.as_table()) else {
+                continue;
+            };
+
+            if let Some(key) = self.find_unknown_key(section) {
+                return Err(anyhow!(
+                    "Unknown setting `{}.{key}` in `{}`",
+                    self.section,
+                    file.path.normalized_display()
+                ));
+            }
+        }
+
+        Ok(self.resolved)
+    }
+
+    fn find_unknown_key<'t>(&self, table: &'t toml::Table) -> Option<&'t String> {
+        table
+            .keys()
+            .find(|key| !self.resolved.iter().any(|r| r.key == key.as_str()))
+    }
+}
+
+/// Command arguments that can get their defaults from the configuration files.
+pub trait Configurable {
+    /// The section in the configuration files.
+    const SECTION: &'static str;
+
+    /// Returns the directory that contains the project configuration file. It is called before
+    /// the arguments are prepared.
+    fn config_dir(&self) -> &Path;
+
+    /// Returns the name of the selected preset.
+    fn preset(&self) -> Option<&str> {
+        None
+    }
+
+    /// Prepares the parsed arguments before the configuration is applied.
+    fn prepare(&mut self, _matches: &ArgMatches) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    /// Resolves the configurable settings.
+    fn resolve(&mut self, resolver: &mut Resolver<'_>) -> anyhow::Result<()>;
+}
+
+/// Applies the project and user configuration to the arguments.
+pub fn apply_config<T: Configurable>(
+    arguments: &mut T,
+    matches: &ArgMatches,
+) -> anyhow::Result<Vec<ResolvedSetting>> {
+    let config = Config::load(arguments.config_dir())?;
+    apply_loaded_config(arguments, matches, &config)
+}
+
+/// Applies the given configuration to the arguments.
+pub fn apply_loaded_config<T: Configurable>(
+    arguments: &mut T,
+    matches: &ArgMatches,
+    config: &Config,
+) -> anyhow::Result<Vec<ResolvedSetting>> {
+    arguments.prepare(matches)?;
+    let preset = arguments
+        .preset()
+        .map(|name| config.preset(name))
+        .transpose()?;
+    let mut resolver = Resolver::new(config, T::SECTION, preset, matches);
+    arguments.resolve(&mut resolver)?;
+    resolver.finish()
+}
+
+impl Configurable for BuildArguments {
+    const SECTION: &'static str = "build";
+
+    fn config_dir(&self) -> &Path {
+        // The configuration is loaded before `prepare` splits off the project directory.
+        match self.positionals.last() {
+            Some(TargetOrDirectory::Directory(dir)) => dir,
+            _ => &self.project_dir,
+        }
+    }
+
+    fn preset(&self) -> Option<&str> {
+        self.preset.as_deref()
+    }
+
+    fn prepare(&mut self, _matches: &ArgMatches) -> anyhow::Result<()> {
+        self.split_positionals()
+    }
+
+    fn resolve(&mut self, r: &mut Resolver<'_>) -> anyhow::Result<()> {
+        let targets_origin = if !self.targets.is_empty() {
+            Some(Origin::CommandLine)
+        } else if let Ok(value) = env::var(ENV_BUILD_TARGET) {
+            // The environment variable is split here, so a project directory that contains a
+            // comma is not split.
+            self.targets = value
+                .split(',')
+                .map(|t| {
+                    BuildOpenTarget::from_str(t.trim(), true)
+                        .map_err(|_| anyhow!("Invalid build target in `{ENV_BUILD_TARGET}`: `{t}`"))
+                })
+                .collect::<anyhow::Result<_>>()?;
+            Some(Origin::Environment)
+        } else {
+            None
+        };
+
+        r.resolve_with_origin("target", targets_origin, &mut self.targets)?;
+        r.resolve_with_origin("all_targets", None, &mut self.all_targets_list)?;
+        r.resolve("keep_going", "keep_going", &mut self.keep_going)?;
+        r.resolve(
+            "skip_if_unchanged",
+            "skip_if_unchanged",
+            &mut self.skip_if_unchanged,
+        )?;
+        r.resolve("profile", "profile", &mut self.profile)?;
+        r.resolve("output", "build_path", &mut self.build_path)?;
+        r.resolve("type", "output_type", &mut self.output_type)?;
+        r.resolve("run", "run_player", &mut self.run_player)?;
+        r.resolve(
+            "development",
+            "development_build",
+            &mut self.development_build,
+        )?;
+        r.resolve("show", "show_built_player", &mut self.show_built_player)?;
+        r.resolve("debugging", "allow_debugging", &mut self.allow_debugging)?;
+        r.resolve(
+            "profiling",
+            "connect_with_profiler",
+            &mut self.connect_with_profiler,
+        )?;
+        r.resolve("deep_profiling", "deep_profiling", &mut self.deep_profiling)?;
+        r.resolve("connect_host", "connect_to_host", &mut self.connect_to_host)?;
+        r.resolve("build_options", "build_options", &mut self.build_options)?;
+        r.resolve("build_args", "build_args", &mut self.build_args)?;
+        r.resolve("defines", "defines", &mut self.defines)?;
+        r.resolve("archive", "archive", &mut self.archive)?;
+        r.resolve(
+            "symbols_archive",
+            "symbols_archive",
+            &mut self.symbols_archive,
+        )?;
+        r.resolve("clean", "clean", &mut self.clean)?;
+        r.resolve("inject", "inject", &mut self.inject)?;
+        r.resolve("mode", "mode", &mut self.mode)?;
+        r.resolve("build_function", "build_function", &mut self.build_function)?;
+        r.resolve("log_file", "log_file", &mut self.log_file)?;
+        r.resolve("retries", "retries", &mut self.retries)?;
+        r.resolve("timeout", "timeout", &mut self.timeout)?;
+        r.resolve("idle_timeout", "idle_timeout", &mut self.idle_timeout)?;
+        r.resolve("quiet", "quiet", &mut self.quiet)?;
+        r.resolve(
+            "force_editor_build",
+            "force_editor_build",
+            &mut self.force_editor_build,
+        )?;
+        r.resolve("unity_args", "args", &mut self.args)
+    }
+}
+
+impl Configurable for TestArguments {
+    const SECTION: &'static str = "test";
+
+    fn config_dir(&self) -> &Path {
+        &self.project_dir
+    }
+
+    fn resolve(&mut self, r: &mut Resolver<'_>) -> anyhow::Result<()> {
+        r.resolve("target", "target", &mut self.target)?;
+        r.resolve("show_results", "show_results", &mut self.show_results)?;
+        r.resolve("no_batch_mode", "no_batch_mode", &mut self.no_batch_mode)?;
+        r.resolve(
+            "forget_project_path",
+            "forget_project_path",
+            &mut self.forget_project_path,
+        )?;
+        r.resolve("categories", "categories", &mut self.categories)?;
+        r.resolve("tests", "tests", &mut self.tests)?;
+        r.resolve("assemblies", "assemblies", &mut self.assemblies)?;
+        r.resolve("retries", "retries", &mut self.retries)?;
+        r.resolve("timeout", "timeout", &mut self.timeout)?;
+        r.resolve("idle_timeout", "idle_timeout", &mut self.idle_timeout)?;
+        r.resolve("quiet", "quiet", &mut self.quiet)
+    }
+}
+
+impl Configurable for OpenArguments {
+    const SECTION: &'static str = "open";
+
+    fn config_dir(&self) -> &Path {
+        &self.project_dir
+    }
+
+    fn resolve(&mut self, r: &mut Resolver<'_>) -> anyhow::Result<()> {
+        r.resolve("target", "target", &mut self.target)?;
+        r.resolve("wait", "wait", &mut self.wait)?;
+        r.resolve("quit", "quit", &mut self.quit)?;
+        r.resolve("quiet", "quiet", &mut self.quiet)
+    }
+}
+
+impl Configurable for NewArguments {
+    const SECTION: &'static str = "new";
+
+    /// The project does not exist yet, the configuration of the current directory is used.
+    fn config_dir(&self) -> &Path {
+        Path::new(".")
+    }
+
+    fn resolve(&mut self, r: &mut Resolver<'_>) -> anyhow::Result<()> {
+        r.resolve("unity", "version_pattern", &mut self.version_pattern)?;
+        r.resolve("target", "target", &mut self.target)?;
+        r.resolve(
+            "add_builder_menu",
+            "add_builder_menu",
+            &mut self.add_builder_menu,
+        )?;
+        r.resolve("lfs", "include_lfs", &mut self.include_lfs)?;
+        r.resolve("no_git", "no_git", &mut self.no_git)?;
+        r.resolve("wait", "wait", &mut self.wait)?;
+        r.resolve("quit", "quit", &mut self.quit)?;
+        r.resolve("quiet", "quiet", &mut self.quiet)
+    }
+}
+
+//
+// Tests
+//
+
+#[cfg(test)]
+mod config_tests {
+    use std::fs;
+
+    use clap::{CommandFactory, FromArgMatches};
+
+    use super::*;
+    use crate::cli::Cli;
+
+    fn build_arguments(args: &[&str], config: &Config) -> anyhow::Result<BuildArguments> {
+        let matches = Cli::command().try_get_matches_from(args)?;
+        let (_, sub_matches) = matches.subcommand().expect("subcommand");
+        let mut arguments = BuildArguments::from_arg_matches(sub_matches)?;
+        apply_loaded_config(&mut arguments, sub_matches, config)?;
+        Ok(arguments)
+    }
+
+    fn write_config(dir: &Path, name: &str, content: &str) -> PathBuf {
+        let path = dir.join(name);
+        fs::write(&path, content).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_project_config_overrides_user_config() {
+        let dir = std::env::temp_dir().join("ucom-config-tests-precedence");
+        fs::create_dir_all(&dir).unwrap();
+        let project = write_config(
+            &dir,
+            "project.toml",
+            "[build]\ntarget = \"android\"\nlog_file = \"build.log\"\ntimeout = \"1h30m\"",
+        );
+        let user = write_config(
+            &dir,
+            "user.toml",
+            "[build]\ntarget = \"ios\"\ndevelopment = true",
+        );
+        let config = Config::from_files(Some(project), Some(user)).unwrap();
+
+        let arguments = build_arguments(&["ucom", "build"], &config).unwrap();
+        assert_eq!(arguments.targets, vec![BuildOpenTarget::Android]);
+        assert!(arguments.development_build);
+        assert_eq!(arguments.log_file, Some(dir.join("build.log")));
+        assert_eq!(arguments.timeout, Some(Duration::from_secs(5400)));
+
+        let arguments = build_arguments(&["ucom", "build", "webgl"], &config).unwrap();
+        assert_eq!(arguments.targets, vec![BuildOpenTarget::WebGL]);
+    }
+
+    #[test]
+    fn test_preset_overrides_environment_and_configuration() {
+        let dir = std::env::temp_dir().join("ucom-config-tests-preset");
+        fs::create_dir_all(&dir).unwrap();
+        let project = write_config(
+            &dir,
+            "project.toml",
+            "[build]\ntarget = \"android\"\nmode = \"editor\"\n\n\
+             [presets.dev]\ntarget = \"ios\"\nbuild_options = [\"strict-mode\"]\n\
+             unity_args = [\"-foo\"]",
+        );
+        let config = Config::from_files(Some(project), None).unwrap();
+
+        let arguments = build_arguments(&["ucom", "build", "--preset", "dev"], &config).unwrap();
+        assert_eq!(arguments.targets, vec![BuildOpenTarget::iOS]);
+        assert_eq!(arguments.mode, BuildMode::Editor);
+        assert_eq!(arguments.build_options, vec![BuildOptions::StrictMode]);
+        assert_eq!(arguments.args, Some(vec!["-foo".to_string()]));
+
+        let arguments =
+            build_arguments(&["ucom", "build", "--preset", "dev", "webgl"], &config).unwrap();
+        assert_eq!(arguments.targets, vec![BuildOpenTarget::WebGL]);
+
+        assert!(build_arguments(&["ucom", "build", "--preset", "release"], &config).is_err());
+    }
+
+    #[test]
+    fn test_multiple_targets_and_all_targets() {
+        let dir = std::env::temp_dir().join("ucom-config-tests-targets");
+        fs::create_dir_all(&dir).unwrap();
+        let project = write_config(
+            &dir,
+            "project.toml",
+            "[build]\nall_targets = [\"win64\", \"webgl\"]",
+        );
+        let config = Config::from_files(Some(project), None).unwrap();
+
+        let arguments =
+            build_arguments(&["ucom", "build", "win64", "linux64", "./project"], &config).unwrap();
+        assert_eq!(
+            arguments.targets,
+            vec![BuildOpenTarget::Win64, BuildOpenTarget::Linux64]
+        );
+        assert_eq!(arguments.project_dir, PathBuf::from("./project"));
+
+        let arguments = build_arguments(&["ucom", "build", "--all-targets"], &config).unwrap();
+        assert_eq!(
+            arguments.all_targets_list,
+            vec![BuildOpenTarget::Win64, BuildOpenTarget::WebGL]
+        );
+
+        // A misspelled target is not mistaken for the project directory.
+        assert!(build_arguments(&["ucom", "build", "win46"], &config).is_err());
+        assert!(build_arguments(&["ucom", "build", "./project", "win64"], &config).is_err());
+    }
+
+    #[test]
+    fn test_invalid_and_unknown_settings_are_errors() {
+        let dir = std::env::temp_dir().join("ucom-config-tests-errors");
+        fs::create_dir_all(&dir).unwrap();
+
+        let invalid = write_config(&dir, "invalid.toml", "[build]\ntarget = \"switch\"");
+        let config = Config::from_files(Some(invalid), None).unwrap();
+        assert!(build_arguments(&["ucom", "build"], &config).is_err());
+
+        let unknown = write_config(&dir, "unknown.toml", "[build]\ntargets = \"ios\"");
+        let config = Config::from_files(Some(unknown), None).unwrap();
+        assert!(build_arguments(&["ucom", "build"], &config).is_err());
+    }
+}
@@ -1,17 +1,22 @@
-use crate::cli::{CacheAction, Cli, Command};
+use crate::cli::{CacheAction, Cli, Command, ConfigAction};
 use crate::commands::test_cmd::run_tests;
 use crate::commands::{
-    INDENT, add_to_project, build_project, find_project_updates, install_latest_matching,
-    list_versions, new_project, open_project, project_info, run_unity,
+    INDENT, InstallMethod, InstallOptions, add_to_project, archive_build_output, build_project,
+    clean_project, collect_unused_versions, editor_command, find_project_updates,
+    install_latest_matching, list_build_presets, list_versions, new_project, open_project,
+    project_info, project_version, run_unity, show_config, uninstall_version,
 };
+use crate::config::apply_config;
 use crate::style_definitions::ERROR;
 use crate::unity::release_api::UpdatePolicy;
+use crate::unity::{CommandError, TIMEOUT_EXIT_CODE};
 use anyhow::Context;
-use clap::Parser;
+use clap::{CommandFactory, FromArgMatches};
 use std::io::IsTerminal;
 use utils::content_cache::{
     configure_cache_from_environment, delete_cache_directory, ucom_cache_dir,
 };
+use utils::interrupt;
 use yansi::Paint;
 
 mod cli;
@@ -21,13 +26,28 @@ mod cli_new;
 mod cli_run;
 mod cli_test;
 mod commands;
+mod config;
 mod nunit;
 mod style_definitions;
 mod unity;
 mod utils;
 
-fn main() -> anyhow::Result<()> {
-    let cli = Cli::parse();
+fn main() {
+    if let Err(e) = run() {
+        eprintln!("Error: {e:?}");
+
+        let timed_out = e
+            .chain()
+            .filter_map(|cause| cause.downcast_ref::<CommandError>())
+            .any(|cause| cause.timed_out);
+        std::process::exit(if timed_out { TIMEOUT_EXIT_CODE } else { 1 });
+    }
+}
+
+fn run() -> anyhow::Result<()> {
+    let matches = Cli::command().get_matches();
+    let cli = Cli::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
+    let sub_matches = matches.subcommand().map_or(&matches, |(_, sub)| sub);
 
     let Some(command) = cli.command else {
         return Ok(());
@@ -40,6 +60,8 @@ fn main() -> anyhow::Result<()> {
     configure_cache_from_environment()
         .with_context(|| "Cannot set cache from environment".paint(ERROR))?;
 
+    interrupt::install_handler()?;
+
     match command {
         Command::List {
             list_type,
@@ -58,11 +80,36 @@ fn main() -> anyhow::Result<()> {
             })
         }
 
-        Command::Install { version } => {
-            install_latest_matching(&version, UpdatePolicy::Incremental)
+        Command::Install {
+            version,
+            hub,
+            modules,
+            accept_eula,
+        } => {
+            let options = InstallOptions {
+                method: if hub {
+                    InstallMethod::UnityHub
+                } else {
+                    InstallMethod::Download
+                },
+                modules,
+                accept_eula,
+            };
+            install_latest_matching(&version, UpdatePolicy::Incremental, &options)
                 .with_context(|| "Cannot install the Unity version".paint(ERROR))
         }
 
+        Command::Uninstall { version, yes } => uninstall_version(&version, yes)
+            .with_context(|| "Cannot uninstall the Unity version".paint(ERROR)),
+
+        Command::Gc {
+            roots,
+            yes,
+            dry_run,
+            ignore_unreadable,
+        } => collect_unused_versions(&roots, yes, dry_run, ignore_unreadable)
+            .with_context(|| "Cannot remove unused Unity versions".paint(ERROR)),
+
         Command::Info {
             project_dir,
             install_required,
@@ -93,24 +140,68 @@ fn main() -> anyhow::Result<()> {
 
         Command::Run(settings) => run_unity(settings).context("Cannot run Unity".paint(ERROR)),
 
-        Command::New(settings) => new_project(settings)
+        Command::New(mut settings) => apply_config(&mut settings, sub_matches)
+            .and_then(|_| new_project(settings))
             .with_context(|| "Cannot create the new Unity project".paint(ERROR)),
 
-        Command::Open(settings) => {
-            open_project(settings).with_context(|| "Cannot open the Unity project".paint(ERROR))
-        }
+        Command::Open(mut settings) => apply_config(&mut settings, sub_matches)
+            .and_then(|_| open_project(settings))
+            .with_context(|| "Cannot open the Unity project".paint(ERROR)),
 
-        Command::Build(settings) => {
-            build_project(&settings).with_context(|| "Cannot build the project".paint(ERROR))
+        Command::Build(settings) if settings.list_presets => {
+            list_build_presets(&settings, sub_matches)
+                .with_context(|| "Cannot list the build presets".paint(ERROR))
         }
 
-        Command::Test(settings) => {
-            run_tests(&settings).with_context(|| "Cannot run tests".paint(ERROR))
-        }
+        Command::Build(mut settings) => apply_config(settings.as_mut(), sub_matches)
+            .and_then(|_| build_project(&settings))
+            .with_context(|| "Cannot build the project".paint(ERROR)),
+
+        Command::Archive {
+            output_dir,
+            project_dir,
+            target,
+            format,
+            symbols,
+        } => archive_build_output(
+            &output_dir,
+            &project_dir,
+            target.as_deref(),
+            format,
+            symbols,
+        )
+        .with_context(|| "Cannot archive the build output".paint(ERROR)),
+
+        Command::Clean {
+            stale_injections,
+            dry_run,
+            project_dir,
+        } => clean_project(&project_dir, stale_injections, dry_run)
+            .with_context(|| "Cannot clean the project".paint(ERROR)),
+
+        Command::Version { action } => project_version(&action)
+            .with_context(|| "Cannot change the project version".paint(ERROR)),
+
+        Command::Editor {
+            action,
+            project_dir,
+            timeout,
+        } => editor_command(&project_dir, &action, timeout)
+            .with_context(|| "Cannot execute the command in the Unity editor".paint(ERROR)),
+
+        Command::Test(mut settings) => apply_config(&mut settings, sub_matches)
+            .and_then(|_| run_tests(&settings))
+            .with_context(|| "Cannot run tests".paint(ERROR)),
 
         Command::Add(arguments) => add_to_project(&arguments)
             .with_context(|| "Cannot add the file to the project".paint(ERROR)),
 
+        Command::Config {
+            action: ConfigAction::Show { project_dir },
+        } => {
+            show_config(&project_dir).with_context(|| "Cannot show the configuration".paint(ERROR))
+        }
+
         Command::Cache { action: command } => {
             match command {
                 CacheAction::Clear => {
@@ -106,6 +106,24 @@ mod test_run_tests {
             2
         );
     }
+
+    #[test]
+    fn test_deserialize_editor_editmode_fail() {
+        // Written by the TestResultsWriter in UnityBuilder.cs for a test run in an open editor.
+        let tr = TestRun::from_file("./src/nunit/test_data/editor-editmode-fail.xml").unwrap();
+        let stats = tr.stats;
+
+        assert_eq!(stats.result, TestResult::Failed);
+        assert_eq!(stats.total, 2);
+        assert_eq!(stats.passed, 1);
+        assert_eq!(stats.failed, 1);
+
+        let tc = tr.test_cases;
+        assert_eq!(tc.len(), 2);
+        assert_eq!(tc[1].full_name, "MyTests.MathTests.Divides");
+        assert_eq!(tc[1].result, TestResult::Failed);
+        assert!(tc[1].failure_message.contains("Expected: 2"));
+    }
 }
 #[cfg(test)]
 mod elements_tests {
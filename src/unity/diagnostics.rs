@@ -0,0 +1,459 @@
+use std::collections::HashSet;
+use std::fmt::{Display, Formatter};
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+
+use anyhow::{Context, anyhow};
+use itertools::Itertools;
+use regex::Regex;
+use yansi::Paint;
+
+use crate::style_definitions::{ERROR, LINK, WARNING};
+use crate::utils::path_ext::PlatformConsistentPathExt;
+
+/// The maximum number of stack trace lines kept for an exception.
+const MAX_STACK_TRACE_LINES: usize = 10;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum DiagnosticKind {
+    /// A C# compiler error or warning, e.g. `CS0103`.
+    Compiler { code: String },
+    /// An error reported by the build pipeline or the build script.
+    Build,
+    /// An exception, e.g. `NullReferenceException`.
+    Exception { name: String },
+}
+
+/// A location in a source file. Paths are as reported by Unity, usually relative to the project.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Location {
+    pub file: PathBuf,
+    pub line: u32,
+    pub column: Option<u32>,
+}
+
+/// An error or warning found in a Unity log.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub kind: DiagnosticKind,
+    pub message: String,
+    pub location: Option<Location>,
+    pub stack_trace: Vec<String>,
+}
+
+impl Diagnostic {
+    fn new(severity: Severity, kind: DiagnosticKind, message: impl Into<String>) -> Self {
+        Self {
+            severity,
+            kind,
+            message: message.into(),
+            location: None,
+            stack_trace: Vec::new(),
+        }
+    }
+}
+
+/// Reads the diagnostics from a Unity log file.
+pub fn parse_log_file(path: &Path) -> anyhow::Result<Vec<Diagnostic>> {
+    let file = File::open(path)
+        .with_context(|| format!("Failed to open log file: {}", path.normalized_display()))?;
+    Ok(parse_log(BufReader::new(file)))
+}
+
+/// Parses the compiler errors and warnings, build errors and exceptions from a Unity log.
+/// Diagnostics that are logged more than once are only returned once.
+pub fn parse_log(reader: impl BufRead) -> Vec<Diagnostic> {
+    let mut lines = reader.lines().map_while(Result::ok).peekable();
+    let mut diagnostics = Vec::new();
+
+    while let Some(line) = lines.next() {
+        let Some(mut diagnostic) = parse_line(line.trim_end()) else {
+            continue;
+        };
+
+        if matches!(diagnostic.kind, DiagnosticKind::Exception { .. }) {
+            // The stack trace follows the exception, up to the next empty line.
+            while let Some(frame) = lines.next_if(|l| !l.trim().is_empty()) {
+                if diagnostic.location.is_none() {
+                    diagnostic.location = parse_stack_frame_location(&frame);
+                }
+                if diagnostic.stack_trace.len() < MAX_STACK_TRACE_LINES {
+                    diagnostic.stack_trace.push(frame.trim().to_string());
+                }
+            }
+        }
+
+        diagnostics.push(diagnostic);
+    }
+
+    let mut seen = HashSet::new();
+    diagnostics.retain(|d| seen.insert(d.clone()));
+    diagnostics
+}
+
+/// Parses a single log line into a diagnostic, without a stack trace.
+fn parse_line(line: &str) -> Option<Diagnostic> {
+    // E.g. `Assets/Scripts/Player.cs(12,5): error CS0103: The name 'x' does not exist`.
+    static COMPILER_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(
+            r"^(?P<file>.+?)\((?P<line>\d+),(?P<column>\d+)\): (?P<severity>error|warning) (?P<code>[A-Z]+\d+): (?P<message>.*)$",
+        )
+        .expect("Invalid regex")
+    });
+
+    // E.g. `CSC : error CS0006: Metadata file 'Game.dll' could not be found`, without a location.
+    static UNLOCATED_COMPILER_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(r"\berror (?P<code>CS\d+): (?P<message>.*)$").expect("Invalid regex")
+    });
+
+    // E.g. `NullReferenceException: Object reference not set to an instance of an object`.
+    static EXCEPTION_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(r"^(?:[\w.]+\.)?(?P<name>\w*Exception): (?P<message>.*)$")
+            .expect("Invalid regex")
+    });
+
+    // Matched anywhere in the line, build scripts often log exceptions behind their own prefix.
+    const BUILD_ERROR_PREFIXES: &[&str] = &[
+        "[Builder] Error:",
+        "Fatal Error",
+        "Error building Player",
+        "BuildFailedException:",
+        "System.Exception:",
+        "error:",
+    ];
+
+    if let Some(caps) = COMPILER_REGEX.captures(line) {
+        let severity = if &caps["severity"] == "error" {
+            Severity::Error
+        } else {
+            Severity::Warning
+        };
+
+        let mut diagnostic = Diagnostic::new(
+            severity,
+            DiagnosticKind::Compiler {
+                code: caps["code"].to_string(),
+            },
+            &caps["message"],
+        );
+        diagnostic.location = Some(Location {
+            file: PathBuf::from(&caps["file"]),
+            line: caps["line"].parse().ok()?,
+            column: caps["column"].parse().ok(),
+        });
+        return Some(diagnostic);
+    }
+
+    if let Some(caps) = UNLOCATED_COMPILER_REGEX.captures(line) {
+        return Some(Diagnostic::new(
+            Severity::Error,
+            DiagnosticKind::Compiler {
+                code: caps["code"].to_string(),
+            },
+            &caps["message"],
+        ));
+    }
+
+    if let Some(caps) = EXCEPTION_REGEX.captures(line) {
+        return Some(Diagnostic::new(
+            Severity::Error,
+            DiagnosticKind::Exception {
+                name: caps["name"].to_string(),
+            },
+            &caps["message"],
+        ));
+    }
+
+    BUILD_ERROR_PREFIXES
+        .iter()
+        .any(|prefix| line.contains(prefix))
+        .then(|| Diagnostic::new(Severity::Error, DiagnosticKind::Build, line.trim()))
+}
+
+/// Returns the location of a Unity stack frame, e.g. `Foo.Bar () (at Assets/Foo.cs:12)`.
+fn parse_stack_frame_location(frame: &str) -> Option<Location> {
+    static FRAME_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(r"\(at (?P<file>.+):(?P<line>\d+)\)\s*$").expect("Invalid regex")
+    });
+
+    let caps = FRAME_REGEX.captures(frame)?;
+    let file = PathBuf::from(&caps["file"]);
+
+    // Frames in Unity's own code point to files that are not in the project.
+    if !(file.starts_with("Assets") || file.starts_with("Packages")) {
+        return None;
+    }
+
+    Some(Location {
+        file,
+        line: caps["line"].parse().ok()?,
+        column: None,
+    })
+}
+
+impl Display for DiagnosticKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Compiler { code } => write!(f, "{code}"),
+            Self::Build => write!(f, "build"),
+            Self::Exception { name } => write!(f, "{name}"),
+        }
+    }
+}
+
+impl Display for Location {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.file.normalized_display(), self.line)?;
+        if let Some(column) = self.column {
+            write!(f, ":{column}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for Diagnostic {
+    /// Formats the diagnostic on a single line.
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match &self.location {
+            Some(location) => write!(f, "{location}: {}", self.message),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
+/// Prints the diagnostics found in the log file and returns an error that summarizes them.
+pub fn report_log_errors(log_file: &Path, project_dir: &Path) -> anyhow::Error {
+    let diagnostics = match parse_log_file(log_file) {
+        Ok(diagnostics) => diagnostics,
+        Err(e) => return e,
+    };
+
+    report_diagnostics(&diagnostics, project_dir)
+        .unwrap_or_else(|| anyhow!("No errors found in log: {}", log_file.normalized_display()))
+}
+
+/// Prints the diagnostics and returns an error that describes the first error and how many more
+/// there are, or `None` if there are no errors.
+pub fn report_diagnostics(diagnostics: &[Diagnostic], project_dir: &Path) -> Option<anyhow::Error> {
+    if diagnostics.is_empty() {
+        return None;
+    }
+
+    println!();
+    print_diagnostics(diagnostics, project_dir);
+
+    let mut errors = diagnostics.iter().filter(|d| d.severity == Severity::Error);
+    match (errors.next(), errors.count()) {
+        (None, _) => None,
+        (Some(error), 0) => Some(anyhow!("{error}")),
+        (Some(error), 1) => Some(anyhow!("{error} (and 1 more error)")),
+        (Some(error), more) => Some(anyhow!("{error} (and {more} more errors)")),
+    }
+}
+
+/// Prints the diagnostics grouped by file, errors first, followed by a count of the errors and
+/// warnings. Source lines are read relative to the project directory.
+pub fn print_diagnostics(diagnostics: &[Diagnostic], project_dir: &Path) {
+    let sorted = diagnostics
+        .iter()
+        // Sorting is stable, so diagnostics without a location keep the order of the log.
+        .sorted_by(|a, b| {
+            (a.severity, a.location.is_none(), &a.location).cmp(&(
+                b.severity,
+                b.location.is_none(),
+                &b.location,
+            ))
+        });
+
+    for diagnostic in sorted {
+        print_diagnostic(diagnostic, project_dir);
+    }
+
+    let count = |severity| {
+        diagnostics
+            .iter()
+            .filter(|d| d.severity == severity)
+            .count()
+    };
+    let (errors, warnings) = (count(Severity::Error), count(Severity::Warning));
+    println!(
+        "{}: {errors} {}, {warnings} {}",
+        "summary".bold(),
+        if errors == 1 { "error" } else { "errors" },
+        if warnings == 1 { "warning" } else { "warnings" },
+    );
+}
+
+fn print_diagnostic(diagnostic: &Diagnostic, project_dir: &Path) {
+    let (label, style) = match diagnostic.severity {
+        Severity::Error => ("error", ERROR),
+        Severity::Warning => ("warning", WARNING),
+    };
+
+    println!(
+        "{}{}",
+        format!("{label}[{}]", diagnostic.kind).paint(style).bold(),
+        format!(": {}", diagnostic.message).bold()
+    );
+
+    let Some(location) = &diagnostic.location else {
+        print_stack_trace(diagnostic, "");
+        println!();
+        return;
+    };
+
+    let line_number = location.line.to_string();
+    let gutter = " ".repeat(line_number.len());
+    println!("{gutter}{} {location}", "-->".paint(LINK));
+
+    if let Some(source_line) = read_source_line(&project_dir.join(&location.file), location.line) {
+        println!("{gutter} {}", "|".paint(LINK));
+        println!(
+            "{} {} {source_line}",
+            line_number.paint(LINK),
+            "|".paint(LINK)
+        );
+        if let Some(column) = location.column {
+            println!(
+                "{gutter} {} {}{}",
+                "|".paint(LINK),
+                caret_indent(&source_line, column),
+                "^".paint(style).bold()
+            );
+        }
+    }
+
+    print_stack_trace(diagnostic, &gutter);
+    println!();
+}
+
+fn print_stack_trace(diagnostic: &Diagnostic, gutter: &str) {
+    for frame in &diagnostic.stack_trace {
+        println!("{gutter} {} {}", "=".paint(LINK), frame.dim());
+    }
+}
+
+/// Returns the whitespace that puts a caret below the given 1-based column of the source line.
+/// Tabs are kept so the caret lines up with the printed source line.
+fn caret_indent(source_line: &str, column: u32) -> String {
+    source_line
+        .chars()
+        .take((column as usize).saturating_sub(1))
+        .map(|c| if c == '\t' { '\t' } else { ' ' })
+        .collect()
+}
+
+fn read_source_line(path: &Path, line: u32) -> Option<String> {
+    let file = File::open(path).ok()?;
+    BufReader::new(file)
+        .lines()
+        .nth((line as usize).checked_sub(1)?)?
+        .ok()
+        .map(|l| l.trim_end().to_string())
+}
+
+//
+// Tests
+//
+
+#[cfg(test)]
+mod diagnostics_tests {
+    use super::*;
+
+    const LOG: &str = "\
+Refreshing native plugins compatible for Editor in 2.38 ms
+Assets/Scripts/Player.cs(12,5): error CS0103: The name 'speed' does not exist in the current context
+Assets/Scripts/Enemy.cs(3,7): warning CS0168: The variable 'e' is declared but never used
+Assets/Scripts/Player.cs(12,5): error CS0103: The name 'speed' does not exist in the current context
+NullReferenceException: Object reference not set to an instance of an object
+Game.Spawner.Spawn () (at Assets/Scripts/Spawner.cs:42)
+UnityEditor.BuildPipeline.BuildPlayer () (at /Users/bokken/build/output/BuildPipeline.cs:10)
+
+[Builder] Error: Build failed with 1 error
+[Build] UnityEditor.Build.BuildFailedException: Missing scenes
+CSC : error CS0006: Metadata file 'Library/ScriptAssemblies/Game.dll' could not be found
+Build Finished, Result: Failure.
+";
+
+    #[test]
+    fn test_parse_log() {
+        let diagnostics = parse_log(LOG.as_bytes());
+        assert_eq!(diagnostics.len(), 6);
+
+        let compiler_error = &diagnostics[0];
+        assert_eq!(compiler_error.severity, Severity::Error);
+        assert_eq!(
+            compiler_error.kind,
+            DiagnosticKind::Compiler {
+                code: "CS0103".to_string()
+            }
+        );
+        assert_eq!(
+            compiler_error.location,
+            Some(Location {
+                file: PathBuf::from("Assets/Scripts/Player.cs"),
+                line: 12,
+                column: Some(5)
+            })
+        );
+
+        assert_eq!(diagnostics[1].severity, Severity::Warning);
+
+        let exception = &diagnostics[2];
+        assert_eq!(
+            exception.kind,
+            DiagnosticKind::Exception {
+                name: "NullReferenceException".to_string()
+            }
+        );
+        assert_eq!(exception.stack_trace.len(), 2);
+        assert_eq!(
+            exception
+                .location
+                .as_ref()
+                .map(|l| (l.file.as_path(), l.line)),
+            Some((Path::new("Assets/Scripts/Spawner.cs"), 42))
+        );
+
+        assert_eq!(diagnostics[3].kind, DiagnosticKind::Build);
+        assert_eq!(
+            diagnostics[3].message,
+            "[Builder] Error: Build failed with 1 error"
+        );
+
+        assert_eq!(diagnostics[4].kind, DiagnosticKind::Build);
+        assert_eq!(
+            diagnostics[4].message,
+            "[Build] UnityEditor.Build.BuildFailedException: Missing scenes"
+        );
+
+        let unlocated = &diagnostics[5];
+        assert_eq!(unlocated.severity, Severity::Error);
+        assert_eq!(
+            unlocated.kind,
+            DiagnosticKind::Compiler {
+                code: "CS0006".to_string()
+            }
+        );
+        assert_eq!(unlocated.location, None);
+        assert_eq!(
+            unlocated.message,
+            "Metadata file 'Library/ScriptAssemblies/Game.dll' could not be found"
+        );
+    }
+
+    #[test]
+    fn test_caret_indent() {
+        assert_eq!(caret_indent("\tint x = y;", 10), "\t        ");
+        assert_eq!(caret_indent("x", 1), "");
+    }
+}
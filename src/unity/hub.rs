@@ -0,0 +1,186 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+
+use serde::Deserialize;
+
+use crate::unity::Version;
+
+/// File in which Unity Hub stores the secondary editor install path.
+const SECONDARY_INSTALL_PATH_FILE: &str = "secondaryInstallPath.json";
+
+/// File in which Unity Hub stores editors, including the ones located manually.
+const EDITORS_V2_FILE: &str = "editors-v2.json";
+
+/// File in which older versions of Unity Hub store manually located editors.
+const EDITORS_LEGACY_FILE: &str = "editors.json";
+
+/// An editor registered in the Unity Hub configuration.
+#[derive(Debug, PartialEq, Eq)]
+pub struct HubEditor {
+    pub version: Version,
+    /// Path as stored by the Hub, which points to the editor executable or application bundle.
+    pub location: PathBuf,
+}
+
+/// Returns the directory where Unity Hub stores its configuration files.
+fn hub_config_dir() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join("UnityHub"))
+}
+
+/// Returns the secondary install path set in Unity Hub, if any.
+pub fn secondary_install_path() -> Option<PathBuf> {
+    let json = fs::read_to_string(hub_config_dir()?.join(SECONDARY_INSTALL_PATH_FILE)).ok()?;
+    parse_secondary_install_path(&json)
+}
+
+/// Returns the editors registered in the Unity Hub configuration.
+/// Missing or unreadable configuration files result in an empty list.
+pub fn registered_editors() -> Vec<HubEditor> {
+    let Some(config_dir) = hub_config_dir() else {
+        return Vec::new();
+    };
+
+    if let Ok(json) = fs::read_to_string(config_dir.join(EDITORS_V2_FILE)) {
+        return parse_editors_v2(&json).unwrap_or_default();
+    }
+
+    fs::read_to_string(config_dir.join(EDITORS_LEGACY_FILE))
+        .ok()
+        .and_then(|json| parse_legacy_editors(&json))
+        .unwrap_or_default()
+}
+
+/// Parses the content of `secondaryInstallPath.json`, which is a single JSON string.
+fn parse_secondary_install_path(json: &str) -> Option<PathBuf> {
+    serde_json::from_str::<String>(json)
+        .ok()
+        .map(|path| path.trim().to_string())
+        .filter(|path| !path.is_empty())
+        .map(PathBuf::from)
+}
+
+#[derive(Deserialize)]
+struct EditorsV2 {
+    data: Vec<EditorEntry>,
+}
+
+#[derive(Deserialize)]
+struct EditorEntry {
+    version: String,
+    location: EditorEntryLocation,
+}
+
+/// The Hub stores the location either as a single path or as a list of paths.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum EditorEntryLocation {
+    Single(String),
+    Multiple(Vec<String>),
+}
+
+impl EditorEntry {
+    fn into_hub_editor(self) -> Option<HubEditor> {
+        let version = self.version.parse().ok()?;
+        let location = match self.location {
+            EditorEntryLocation::Single(location) => location,
+            EditorEntryLocation::Multiple(locations) => locations.into_iter().next()?,
+        };
+
+        Some(HubEditor {
+            version,
+            location: PathBuf::from(location),
+        })
+    }
+}
+
+/// Parses the content of `editors-v2.json`.
+fn parse_editors_v2(json: &str) -> Option<Vec<HubEditor>> {
+    let editors: EditorsV2 = serde_json::from_str(json).ok()?;
+    Some(
+        editors
+            .data
+            .into_iter()
+            .filter_map(EditorEntry::into_hub_editor)
+            .collect(),
+    )
+}
+
+/// Parses the content of the legacy `editors.json`, which maps versions to entries.
+fn parse_legacy_editors(json: &str) -> Option<Vec<HubEditor>> {
+    let editors: BTreeMap<String, EditorEntry> = serde_json::from_str(json).ok()?;
+    Some(
+        editors
+            .into_values()
+            .filter_map(EditorEntry::into_hub_editor)
+            .collect(),
+    )
+}
+
+//
+// Tests
+//
+
+#[cfg(test)]
+mod hub_tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_secondary_install_path() {
+        assert_eq!(
+            parse_secondary_install_path(r#""/Volumes/Data/Unity""#),
+            Some(PathBuf::from("/Volumes/Data/Unity"))
+        );
+        assert_eq!(parse_secondary_install_path(r#""""#), None);
+        assert_eq!(parse_secondary_install_path("not json"), None);
+    }
+
+    #[test]
+    fn test_parse_editors_v2() {
+        let json = r#"{
+            "schema_version": "v2",
+            "data": [
+                {
+                    "version": "2022.3.5f1",
+                    "location": ["/Volumes/Data/Unity/2022.3.5f1/Unity.app"],
+                    "manual": true,
+                    "architecture": "arm64",
+                    "productName": "Unity"
+                },
+                {
+                    "version": "not-a-version",
+                    "location": ["/somewhere/Unity.app"],
+                    "manual": true
+                }
+            ]
+        }"#;
+
+        let editors = parse_editors_v2(json).unwrap();
+        assert_eq!(
+            editors,
+            vec![HubEditor {
+                version: "2022.3.5f1".parse().unwrap(),
+                location: PathBuf::from("/Volumes/Data/Unity/2022.3.5f1/Unity.app"),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_parse_legacy_editors() {
+        let json = r#"{
+            "2021.3.1f1": {
+                "version": "2021.3.1f1",
+                "location": "/opt/unity/2021.3.1f1/Editor/Unity",
+                "manual": true
+            }
+        }"#;
+
+        let editors = parse_legacy_editors(json).unwrap();
+        assert_eq!(editors.len(), 1);
+        assert_eq!(editors[0].version, "2021.3.1f1".parse().unwrap());
+        assert_eq!(
+            editors[0].location,
+            PathBuf::from("/opt/unity/2021.3.1f1/Editor/Unity")
+        );
+    }
+}
@@ -1,16 +1,20 @@
 use crate::unity::Version;
+use crate::unity::hub;
 use crate::utils::path_ext::PlatformConsistentPathExt;
 use crate::utils::vec1::{Vec1, Vec1Error};
 use anyhow::{Context, anyhow};
 use itertools::Itertools;
+use std::collections::BTreeMap;
 use std::ops::Deref;
 use std::path::{Path, PathBuf};
-use std::sync::LazyLock;
+use std::sync::{Arc, Mutex, PoisonError};
 use std::{env, fs};
 
 const ENV_EDITOR_DIR: &str = "UCOM_EDITOR_DIR";
 
 mod platform {
+    use std::path::PathBuf;
+
     /// Sub path to the executable on macOS.
     #[cfg(target_os = "macos")]
     pub const UNITY_EDITOR_EXE: &str = "Unity.app/Contents/MacOS/Unity";
@@ -19,21 +23,55 @@ mod platform {
     #[cfg(target_os = "windows")]
     pub const UNITY_EDITOR_EXE: &str = r"Editor\Unity.exe";
 
+    /// Sub path to the executable on Linux.
+    #[cfg(target_os = "linux")]
+    pub const UNITY_EDITOR_EXE: &str = "Editor/Unity";
+
     /// Other target platforms are not supported.
-    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    #[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
     pub const UNITY_EDITOR_EXE: &str = compile_error!("Unsupported platform");
 
+    /// Sub path to the build support modules on macOS.
+    #[cfg(target_os = "macos")]
+    pub const PLAYBACK_ENGINES_DIR: &str = "PlaybackEngines";
+
+    /// Sub path to the build support modules on Windows.
+    #[cfg(target_os = "windows")]
+    pub const PLAYBACK_ENGINES_DIR: &str = r"Editor\Data\PlaybackEngines";
+
+    /// Sub path to the build support modules on Linux.
+    #[cfg(target_os = "linux")]
+    pub const PLAYBACK_ENGINES_DIR: &str = "Editor/Data/PlaybackEngines";
+
+    /// Other target platforms are not supported.
+    #[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+    pub const PLAYBACK_ENGINES_DIR: &str = compile_error!("Unsupported platform");
+
     /// Parent directory of editor installations on macOS.
     #[cfg(target_os = "macos")]
-    pub const UNITY_EDITOR_DIR: &str = "/Applications/Unity/Hub/Editor/";
+    pub fn default_editor_dir() -> PathBuf {
+        PathBuf::from("/Applications/Unity/Hub/Editor/")
+    }
 
     /// Parent directory of editor installations on Windows.
     #[cfg(target_os = "windows")]
-    pub const UNITY_EDITOR_DIR: &str = r"C:\Program Files\Unity\Hub\Editor";
+    pub fn default_editor_dir() -> PathBuf {
+        PathBuf::from(r"C:\Program Files\Unity\Hub\Editor")
+    }
+
+    /// Parent directory of editor installations on Linux, relative to the user's home directory.
+    #[cfg(target_os = "linux")]
+    pub fn default_editor_dir() -> PathBuf {
+        dirs::home_dir()
+            .unwrap_or_default()
+            .join("Unity/Hub/Editor")
+    }
 
     /// Other target platforms are not supported.
-    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
-    pub const UNITY_EDITOR_DIR: &str = compile_error!("Unsupported platform");
+    #[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+    pub fn default_editor_dir() -> PathBuf {
+        compile_error!("Unsupported platform")
+    }
 }
 
 //
@@ -69,29 +107,6 @@ impl SortedVersions {
         self.0.into()
     }
 
-    /// Returns a sorted list of installed Unity versions from the given directory or an error if no versions are found.
-    fn from_dir(dir: impl AsRef<Path>) -> anyhow::Result<Self> {
-        let versions = fs::read_dir(&dir)
-            .with_context(|| {
-                format!(
-                    "Cannot read available Unity editors in `{}`",
-                    dir.as_ref().normalized_display()
-                )
-            })?
-            .flatten()
-            .map(|de| de.path())
-            .filter(|p| p.is_dir() && p.join(platform::UNITY_EDITOR_EXE).exists())
-            .filter_map(|p| p.file_name()?.to_string_lossy().parse::<Version>().ok())
-            .collect_vec();
-
-        Self::try_from(versions).map_err(|_| {
-            anyhow!(
-                "No Unity installations found in `{}`",
-                dir.as_ref().normalized_display()
-            )
-        })
-    }
-
     pub fn filter_by_prefix(self, version_prefix: Option<&str>) -> anyhow::Result<Self> {
         let Some(version_prefix) = version_prefix else {
             // No version to match, return the full list again.
@@ -112,20 +127,81 @@ impl SortedVersions {
 // Installations
 //
 
-/// The installed versions and the root directory they are installed in.
+/// The location of an installed editor.
+#[derive(Debug, Clone)]
+pub struct EditorLocation {
+    /// The root directory the editor was found in.
+    pub root: PathBuf,
+    /// The installation directory of the editor.
+    pub dir: PathBuf,
+}
+
+/// Short names of the build support modules by the name of their `PlaybackEngines` directory.
+const MODULE_DIRECTORIES: &[(&str, &str)] = &[
+    ("AndroidPlayer", "android"),
+    ("AppleTVSupport", "appletv"),
+    ("iOSSupport", "ios"),
+    ("LinuxStandaloneSupport", "linux"),
+    ("MacStandaloneSupport", "mac"),
+    ("MetroSupport", "uwp"),
+    ("VisionOSPlayer", "visionos"),
+    ("WebGLSupport", "webgl"),
+    ("WindowsStandaloneSupport", "windows"),
+];
+
+impl EditorLocation {
+    /// Returns the short names of the build support modules installed in the editor.
+    /// Unknown modules are returned by the name of their directory.
+    pub fn installed_modules(&self) -> Vec<String> {
+        let Ok(entries) = fs::read_dir(self.dir.join(platform::PLAYBACK_ENGINES_DIR)) else {
+            return Vec::new();
+        };
+
+        entries
+            .flatten()
+            .filter(|de| de.path().is_dir())
+            .map(|de| {
+                let dir_name = de.file_name().to_string_lossy().into_owned();
+                MODULE_DIRECTORIES
+                    .iter()
+                    .find(|(dir, _)| dir.eq_ignore_ascii_case(&dir_name))
+                    .map_or(dir_name, |(_, name)| (*name).to_string())
+            })
+            .sorted()
+            .collect()
+    }
+}
+
+/// The editors found on this machine, discovered when they are first needed.
+static DISCOVERED_EDITORS: Mutex<Option<Arc<DiscoveredEditors>>> = Mutex::new(None);
+
+/// The editors found in the editor root directories and in the Unity Hub configuration.
+struct DiscoveredEditors {
+    roots: Vec<PathBuf>,
+    editors: BTreeMap<Version, EditorLocation>,
+}
+
+/// The installed versions and the root directories they are installed in.
 pub struct Installations {
-    pub install_dir: PathBuf,
+    pub roots: Vec<PathBuf>,
     pub versions: SortedVersions,
+    locations: BTreeMap<Version, EditorLocation>,
 }
 
 impl Installations {
     /// Returns a list of installed Unity versions or an error if no versions are found.
     pub fn find_installations(version_prefix: Option<&str>) -> anyhow::Result<Self> {
-        let install_dir = Self::editor_parent_dir()?.to_path_buf();
-        let versions = SortedVersions::from_dir(&install_dir)?.filter_by_prefix(version_prefix)?;
+        let discovered = Self::discovered_editors()?;
+        let versions = Self::installed_versions(&discovered)?.filter_by_prefix(version_prefix)?;
+        let locations = versions
+            .iter()
+            .filter_map(|v| discovered.editors.get(v).map(|l| (*v, l.clone())))
+            .collect();
+
         Ok(Self {
-            install_dir,
+            roots: discovered.roots.clone(),
             versions,
+            locations,
         })
     }
 
@@ -136,99 +212,182 @@ impl Installations {
 
     /// Returns the version of the latest-installed version that matches the given prefix.
     pub fn latest_installed_version(version_prefix: Option<&str>) -> anyhow::Result<Version> {
-        let version = *SortedVersions::from_dir(Self::editor_parent_dir()?)?
+        let discovered = Self::discovered_editors()?;
+        let version = *Self::installed_versions(&discovered)?
             .filter_by_prefix(version_prefix)?
             .last();
         Ok(version)
     }
 
-    /// Returns the parent directory of the editor installations.
-    fn editor_parent_dir() -> anyhow::Result<&'static Path> {
-        static EDITOR_PARENT_DIR: LazyLock<anyhow::Result<PathBuf>> = LazyLock::new(|| {
-            let path = Installations::resolve_unity_editor_directory()?;
-            Installations::verify_directory_contains_unity_installations(&path)?;
-            Ok(path)
-        });
-
-        match EDITOR_PARENT_DIR.as_deref() {
-            Ok(path) => Ok(path),
-            Err(..) => Err(Self::create_unity_installation_not_found_error()),
+    /// Returns the root directory in which new editors are installed: the first directory set by
+    /// `UCOM_EDITOR_DIR`, the secondary install path of Unity Hub or the default editor directory.
+    pub fn install_root() -> PathBuf {
+        if let Some(paths) = env::var_os(ENV_EDITOR_DIR)
+            && let Some(path) = env::split_paths(&paths).find(|p| !p.as_os_str().is_empty())
+        {
+            return path;
         }
+
+        hub::secondary_install_path().unwrap_or_else(platform::default_editor_dir)
+    }
+
+    /// Returns the location of the given installed version.
+    pub fn location(&self, version: Version) -> Option<&EditorLocation> {
+        self.locations.get(&version)
+    }
+
+    /// Returns true if the installed versions were found in more than one root directory.
+    pub fn has_multiple_roots(&self) -> bool {
+        self.locations.values().map(|l| &l.root).unique().count() > 1
+    }
+
+    /// Returns the sorted list of all discovered versions.
+    fn installed_versions(discovered: &DiscoveredEditors) -> anyhow::Result<SortedVersions> {
+        SortedVersions::try_from(discovered.editors.keys().copied().collect_vec())
+            .map_err(|_| Self::create_unity_installation_not_found_error())
     }
 
-    /// Resolves the Unity editor directory from the environment variable or the default path.
-    fn resolve_unity_editor_directory() -> anyhow::Result<PathBuf> {
-        // Try to get the directory from the environment variable.
-        if let Some(path) = env::var_os(ENV_EDITOR_DIR) {
-            // Use the directory set by the environment variable.
-            let path = Path::new(&path);
-            if path.is_dir() {
-                Ok(path.to_owned())
-            } else {
-                Err(anyhow!(
-                    "Editor directory set by `{ENV_EDITOR_DIR}` is not a valid directory: `{}`",
-                    path.normalized_display()
-                ))
+    /// Returns the editors found on this machine. The result is cached until an editor is
+    /// installed or removed, see [`Installations::refresh`].
+    fn discovered_editors() -> anyhow::Result<Arc<DiscoveredEditors>> {
+        let mut cache = DISCOVERED_EDITORS
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+
+        if let Some(discovered) = cache.as_ref() {
+            return Ok(Arc::clone(discovered));
+        }
+
+        let discovered = Arc::new(Self::discover_editors()?);
+        *cache = Some(Arc::clone(&discovered));
+        Ok(discovered)
+    }
+
+    /// Discards the cached editors, so they are discovered again after an editor was installed or
+    /// removed.
+    pub fn refresh() {
+        *DISCOVERED_EDITORS
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner) = None;
+    }
+
+    /// Scans the editor root directories and the Unity Hub configuration for installed editors.
+    /// When `UCOM_EDITOR_DIR` is set, only the directories it lists are scanned.
+    fn discover_editors() -> anyhow::Result<DiscoveredEditors> {
+        let roots = Self::resolve_unity_editor_directories()?;
+        let mut editors = BTreeMap::new();
+
+        for root in &roots {
+            for (version, dir) in Self::scan_editor_directory(root)? {
+                editors.entry(version).or_insert_with(|| EditorLocation {
+                    root: root.clone(),
+                    dir,
+                });
             }
-        } else {
-            // Use the default directory.
-            let path = Path::new(platform::UNITY_EDITOR_DIR);
-            if path.is_dir() {
-                Ok(path.to_owned())
-            } else {
-                Err(anyhow!(
-                    "The default editor directory `{}` is not a valid directory`",
-                    platform::UNITY_EDITOR_DIR
-                ))
+        }
+
+        if env::var_os(ENV_EDITOR_DIR).is_none() {
+            // Add editors that were installed elsewhere or located manually in Unity Hub.
+            for hub_editor in hub::registered_editors() {
+                let Some(dir) = Self::editor_dir_from_location(&hub_editor.location) else {
+                    continue;
+                };
+
+                editors
+                    .entry(hub_editor.version)
+                    .or_insert_with(|| EditorLocation {
+                        root: dir.parent().map(Path::to_path_buf).unwrap_or_default(),
+                        dir,
+                    });
             }
         }
+
+        let mut roots = roots;
+        for location in editors.values() {
+            if !roots.contains(&location.root) {
+                roots.push(location.root.clone());
+            }
+        }
+
+        Ok(DiscoveredEditors { roots, editors })
     }
 
-    /// Check if the directory contains Unity installations.
-    fn verify_directory_contains_unity_installations(dir: impl AsRef<Path>) -> anyhow::Result<()> {
-        let dir = dir.as_ref();
-        let has_installations = dir
-            .read_dir()
+    /// Returns the installed versions in the given directory and their installation directories.
+    fn scan_editor_directory(dir: &Path) -> anyhow::Result<Vec<(Version, PathBuf)>> {
+        let editors = fs::read_dir(dir)
             .with_context(|| {
                 format!(
-                    "Cannot read editor directory `{}`",
+                    "Cannot read available Unity editors in `{}`",
                     dir.normalized_display()
                 )
             })?
-            .filter_map(Result::ok)
-            .any(|de| de.path().join(platform::UNITY_EDITOR_EXE).exists());
+            .flatten()
+            .map(|de| de.path())
+            .filter(|p| p.is_dir() && p.join(platform::UNITY_EDITOR_EXE).exists())
+            .filter_map(|p| {
+                let version = p.file_name()?.to_string_lossy().parse::<Version>().ok()?;
+                Some((version, p))
+            })
+            .collect_vec();
+
+        Ok(editors)
+    }
 
-        if has_installations {
-            Ok(())
-        } else {
-            Err(Self::create_unity_installation_not_found_error())
+    /// Returns the installation directory of an editor from the location stored by Unity Hub.
+    fn editor_dir_from_location(location: &Path) -> Option<PathBuf> {
+        location
+            .ancestors()
+            .find(|dir| dir.join(platform::UNITY_EDITOR_EXE).exists())
+            .map(Path::to_path_buf)
+    }
+
+    /// Resolves the Unity editor directories from the environment variable or the default paths.
+    /// The environment variable can contain multiple paths, separated like the `PATH` variable.
+    fn resolve_unity_editor_directories() -> anyhow::Result<Vec<PathBuf>> {
+        // Try to get the directories from the environment variable.
+        if let Some(paths) = env::var_os(ENV_EDITOR_DIR) {
+            // Use the directories set by the environment variable.
+            return env::split_paths(&paths)
+                .filter(|path| !path.as_os_str().is_empty())
+                .map(|path| {
+                    if path.is_dir() {
+                        Ok(path)
+                    } else {
+                        Err(anyhow!(
+                            "Editor directory set by `{ENV_EDITOR_DIR}` is not a valid directory: `{}`",
+                            path.normalized_display()
+                        ))
+                    }
+                })
+                .collect();
         }
+
+        // Use the default directory and the secondary install path configured in Unity Hub.
+        let roots = std::iter::once(platform::default_editor_dir())
+            .chain(hub::secondary_install_path())
+            .filter(|path| path.is_dir())
+            .unique()
+            .collect();
+
+        Ok(roots)
     }
 
     /// Creates an error indicating that no Unity installations were found.
     fn create_unity_installation_not_found_error() -> anyhow::Error {
         // Could not find any installations, check if the editor directory is set.
         match env::var_os(ENV_EDITOR_DIR) {
-            Some(path) => {
-                if Path::new(&path).is_dir() {
-                    // The editor directory is set, but does not contain any installations.
-                    anyhow!(
-                        "No Unity installations found in the editor directory `{}`. Please set the `{ENV_EDITOR_DIR}` environment variable to the correct path.",
-                        path.to_string_lossy()
-                    )
-                } else {
-                    // The editor directory is set,but it is not valid.
-                    anyhow!(
-                        "The editor directory set by `{ENV_EDITOR_DIR}` is not valid: `{}`",
-                        path.to_string_lossy()
-                    )
-                }
+            Some(paths) => {
+                // The editor directories are set, but do not contain any installations.
+                anyhow!(
+                    "No Unity installations found in the editor directories `{}`. Please set the `{ENV_EDITOR_DIR}` environment variable to the correct path.",
+                    paths.to_string_lossy()
+                )
             }
             None => {
                 // The editor directory is not set and no installations were found.
                 anyhow!(
-                    "No Unity installations found in `{}`. Set `{ENV_EDITOR_DIR}` or install Unity.",
-                    platform::UNITY_EDITOR_DIR
+                    "No Unity installations found in `{}` or in the Unity Hub configuration. Set `{ENV_EDITOR_DIR}` or install Unity.",
+                    platform::default_editor_dir().normalized_display()
                 )
             }
         }
@@ -238,21 +397,27 @@ impl Installations {
 impl Version {
     /// Returns true if the editor is installed.
     pub fn is_editor_installed(self) -> anyhow::Result<bool> {
-        Ok(Installations::editor_parent_dir()?
-            .join(self.to_interned_str())
-            .exists())
+        Ok(Installations::discovered_editors()?
+            .editors
+            .contains_key(&self))
+    }
+
+    /// Returns the installation directory of the editor.
+    pub fn editor_dir(self) -> anyhow::Result<PathBuf> {
+        Installations::discovered_editors()?
+            .editors
+            .get(&self)
+            .map(|location| location.dir.clone())
+            .ok_or_else(|| anyhow!("Unity version is not installed: {self}"))
     }
 
     /// Returns the path to the editor executable.
     pub fn editor_executable_path(self) -> anyhow::Result<PathBuf> {
-        let exe_path = Installations::editor_parent_dir()?
-            .join(self.to_interned_str())
-            .join(platform::UNITY_EDITOR_EXE);
-
-        if exe_path.exists() {
-            Ok(exe_path)
-        } else {
-            Err(anyhow!("Unity version is not installed: {self}"))
-        }
+        Installations::discovered_editors()?
+            .editors
+            .get(&self)
+            .map(|location| location.dir.join(platform::UNITY_EDITOR_EXE))
+            .filter(|exe_path| exe_path.exists())
+            .ok_or_else(|| anyhow!("Unity version is not installed: {self}"))
     }
 }
@@ -0,0 +1,507 @@
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use anyhow::{Context, anyhow};
+use serde::Deserialize;
+use walkdir::WalkDir;
+
+use crate::unity::installations::Installations;
+use crate::unity::release_api_data::{
+    DownloadsElement, EulaElement, ExtractedPathRename, ModulesElement, ReleaseData,
+    SubModulesElement,
+};
+use crate::utils::download::{download_file, verify_integrity};
+use crate::utils::path_ext::PlatformConsistentPathExt;
+use crate::utils::spawn_cmd::CommandError;
+use crate::utils::status_line::{MessageType, StatusLine};
+
+/// Replaces the scheme and host of download urls, e.g. `http://localhost:8080`.
+const ENV_DOWNLOAD_MIRROR: &str = "UCOM_DOWNLOAD_MIRROR";
+
+/// The platform name used by the release API for the host.
+const HOST_PLATFORM: &str = if cfg!(target_os = "macos") {
+    "MAC_OS"
+} else if cfg!(target_os = "windows") {
+    "WINDOWS"
+} else {
+    "LINUX"
+};
+
+/// The architecture name used by the release API for the host.
+const HOST_ARCHITECTURE: &str = if cfg!(target_arch = "aarch64") {
+    "ARM64"
+} else {
+    "X86_64"
+};
+
+/// Returns the editor download for the host platform and architecture.
+pub fn host_editor_download(release: &ReleaseData) -> anyhow::Result<&DownloadsElement> {
+    release
+        .downloads
+        .iter()
+        .flatten()
+        .find(|d| d.platform == HOST_PLATFORM && d.architecture == HOST_ARCHITECTURE)
+        .ok_or_else(|| {
+            anyhow!(
+                "No {HOST_PLATFORM} ({HOST_ARCHITECTURE}) download available for version {}",
+                release.version
+            )
+        })
+}
+
+/// Returns the url to download from, with the host replaced by the mirror if `UCOM_DOWNLOAD_MIRROR` is set.
+pub fn download_url(url: &str) -> String {
+    match env::var(ENV_DOWNLOAD_MIRROR) {
+        Ok(mirror) if !mirror.is_empty() => replace_url_host(url, &mirror),
+        _ => url.to_string(),
+    }
+}
+
+/// Replaces the scheme and host of the url with the given base url.
+fn replace_url_host(url: &str, base_url: &str) -> String {
+    let path = url
+        .split_once("://")
+        .and_then(|(_, rest)| rest.find('/').map(|i| &rest[i..]))
+        .unwrap_or("/");
+
+    format!("{}{path}", base_url.trim_end_matches('/'))
+}
+
+/// Downloads the file and verifies its integrity if the release data provides a hash.
+/// Returns the path to the downloaded file.
+pub fn download_verified(
+    url: &str,
+    integrity: Option<&str>,
+    description: &str,
+    download_dir: &Path,
+) -> anyhow::Result<PathBuf> {
+    let url = download_url(url);
+    let file_name = url
+        .rsplit('/')
+        .next()
+        .filter(|name| !name.is_empty())
+        .unwrap_or("download");
+    let path = download_dir.join(file_name);
+
+    let status = StatusLine::new("Downloading", description);
+    download_file(&url, &path, |count, total| {
+        let progress = match total {
+            Some(total) if total > 0 => {
+                format!("{:.0}%", count as f64 / total as f64 * 100.0)
+            }
+            _ => format!("{} MB", count / (1024 * 1024)),
+        };
+        status.update_line("Downloading", format!("{description} ({progress})"));
+    })?;
+
+    match integrity {
+        Some(integrity) => {
+            status.update_line("Verifying", description);
+            verify_integrity(&path, integrity)?;
+        }
+        None => MessageType::print_line(
+            "Warning",
+            format!("No integrity hash available for {description}, skipping verification"),
+            MessageType::Warning,
+        ),
+    }
+
+    Ok(path)
+}
+
+/// Downloads the editor for the host platform and installs it in the root directory.
+/// Returns the installation directory of the editor.
+pub fn install_editor(release: &ReleaseData, root: &Path) -> anyhow::Result<PathBuf> {
+    let download = host_editor_download(release)?;
+    let editor_dir = root.join(release.version.to_interned_str());
+
+    if editor_dir.exists() {
+        return Err(anyhow!(
+            "Installation directory already exists: `{}`",
+            editor_dir.normalized_display()
+        ));
+    }
+
+    // Stage the download next to the installation so the extracted editor can be moved in place.
+    let staging_dir = root.join(format!(".ucom-install-{}", release.version));
+    fs::create_dir_all(&staging_dir).with_context(|| {
+        format!(
+            "Cannot create directory `{}`",
+            staging_dir.normalized_display()
+        )
+    })?;
+
+    let result = download_and_extract_editor(release, download, &staging_dir, &editor_dir);
+    fs::remove_dir_all(&staging_dir).ok();
+
+    if result.is_err() && editor_dir.exists() {
+        // Do not leave a partial installation behind.
+        fs::remove_dir_all(&editor_dir).ok();
+    }
+    Installations::refresh();
+
+    result.map(|()| editor_dir)
+}
+
+fn download_and_extract_editor(
+    release: &ReleaseData,
+    download: &DownloadsElement,
+    staging_dir: &Path,
+    editor_dir: &Path,
+) -> anyhow::Result<()> {
+    let description = format!("Unity {}", release.version);
+    let archive = download_verified(
+        &download.url,
+        download.integrity.as_deref(),
+        &description,
+        staging_dir,
+    )?;
+
+    let _status = StatusLine::new("Installing", &description);
+    match download.download_type.as_str() {
+        "TAR_XZ" => {
+            // The archive contains the `Editor` directory.
+            let extract_dir = staging_dir.join("extracted");
+            extract_tar(&archive, &extract_dir)?;
+            fs::rename(&extract_dir, editor_dir)?;
+        }
+        "PKG" => {
+            let expand_dir = staging_dir.join("expanded");
+            expand_pkg(&archive, &expand_dir)?;
+            let app = find_directory(&expand_dir, "Unity.app")?;
+            fs::create_dir_all(editor_dir)?;
+            fs::rename(app, editor_dir.join("Unity.app"))?;
+        }
+        "EXE" => run_installer(&archive, editor_dir)?,
+        other => return Err(anyhow!("Unsupported download type `{other}`")),
+    }
+
+    Ok(())
+}
+
+//
+// Modules
+//
+
+/// Placeholder in module paths for the installation directory of the editor.
+const UNITY_PATH_PLACEHOLDER: &str = "{UNITY_PATH}";
+
+/// The file in the editor installation directory in which Unity Hub keeps the module metadata.
+const HUB_MODULES_FILE: &str = "modules.json";
+
+/// A module as described by Unity Hub in `modules.json`.
+#[derive(Deserialize)]
+struct HubModule {
+    id: String,
+    /// Only written by newer versions of Unity Hub.
+    #[serde(rename = "isInstalled")]
+    is_installed: Option<bool>,
+    /// Older versions of Unity Hub mark the installed modules as selected.
+    #[serde(default)]
+    selected: bool,
+}
+
+/// A module or sub-module of an editor release.
+#[derive(Debug)]
+pub struct Module<'a> {
+    pub id: &'a str,
+    pub name: &'a str,
+    pub url: &'a str,
+    pub integrity: Option<&'a str>,
+    pub module_type: &'a str,
+    pub destination: Option<&'a str>,
+    pub extracted_path_rename: Option<&'a ExtractedPathRename>,
+    pub eula: &'a [EulaElement],
+}
+
+impl<'a> From<&'a ModulesElement> for Module<'a> {
+    fn from(module: &'a ModulesElement) -> Self {
+        Self {
+            id: &module.id,
+            name: &module.name,
+            url: &module.url,
+            integrity: module.integrity.as_deref(),
+            module_type: &module.module_type,
+            destination: module.destination.as_deref(),
+            extracted_path_rename: module.extracted_path_rename.as_ref(),
+            eula: module.eula.as_deref().unwrap_or_default(),
+        }
+    }
+}
+
+impl<'a> From<&'a SubModulesElement> for Module<'a> {
+    fn from(module: &'a SubModulesElement) -> Self {
+        Self {
+            id: &module.id,
+            name: &module.name,
+            url: &module.url,
+            integrity: module.integrity.as_deref(),
+            module_type: &module.module_type,
+            destination: module.destination.as_deref(),
+            extracted_path_rename: module.extracted_path_rename.as_ref(),
+            eula: module.eula.as_deref().unwrap_or_default(),
+        }
+    }
+}
+
+/// Returns the requested modules followed by their required and pre-selected sub-modules.
+pub fn resolve_modules<'a>(
+    download: &'a DownloadsElement,
+    ids: &[String],
+) -> anyhow::Result<Vec<Module<'a>>> {
+    let mut modules = Vec::new();
+
+    for id in ids {
+        let module = download
+            .modules
+            .iter()
+            .find(|m| m.id.eq_ignore_ascii_case(id))
+            .ok_or_else(|| {
+                anyhow!(
+                    "Unknown module `{id}`, available modules: {}",
+                    download
+                        .modules
+                        .iter()
+                        .filter(|m| !m.hidden)
+                        .map(|m| m.id.as_str())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )
+            })?;
+
+        modules.push(Module::from(module));
+        collect_sub_modules(&module.sub_modules, &mut modules);
+    }
+
+    Ok(modules)
+}
+
+fn collect_sub_modules<'a>(sub_modules: &'a [SubModulesElement], modules: &mut Vec<Module<'a>>) {
+    for sub_module in sub_modules.iter().filter(|m| m.required || m.pre_selected) {
+        modules.push(Module::from(sub_module));
+        collect_sub_modules(&sub_module.sub_modules, modules);
+    }
+}
+
+/// Replaces the `{UNITY_PATH}` placeholder with the installation directory of the editor.
+fn resolve_module_path(path: &str, editor_dir: &Path) -> PathBuf {
+    match path.strip_prefix(UNITY_PATH_PLACEHOLDER) {
+        Some(rest) => editor_dir.join(rest.trim_start_matches(['/', '\\'])),
+        None => PathBuf::from(path),
+    }
+}
+
+/// Downloads and installs the module in the editor installation directory.
+/// Returns false if the module was already installed.
+pub fn install_module(module: &Module<'_>, editor_dir: &Path) -> anyhow::Result<bool> {
+    let destination = module.destination.map_or_else(
+        || editor_dir.to_path_buf(),
+        |d| resolve_module_path(d, editor_dir),
+    );
+
+    // Modules that are renamed after extraction share their destination with other modules.
+    let installed_path = match module.extracted_path_rename {
+        Some(rename) => resolve_module_path(&rename.to, editor_dir),
+        None => destination.clone(),
+    };
+
+    // Modules without a destination, like the Windows installers, can only be recognized from
+    // the Unity Hub metadata.
+    let is_installed = match module.destination {
+        Some(_) => installed_path.exists(),
+        None => is_installed_by_hub(module.id, editor_dir),
+    };
+    if is_installed {
+        return Ok(false);
+    }
+
+    let dir_name = editor_dir
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let staging_dir = editor_dir.with_file_name(format!(".ucom-install-{dir_name}-{}", module.id));
+    fs::create_dir_all(&staging_dir).with_context(|| {
+        format!(
+            "Cannot create directory `{}`",
+            staging_dir.normalized_display()
+        )
+    })?;
+
+    let result = download_and_extract_module(module, editor_dir, &destination, &staging_dir);
+    fs::remove_dir_all(&staging_dir).ok();
+    result.map(|()| true)
+}
+
+/// Returns true if the Unity Hub metadata of the editor lists the module as installed.
+fn is_installed_by_hub(module_id: &str, editor_dir: &Path) -> bool {
+    fs::read_to_string(editor_dir.join(HUB_MODULES_FILE))
+        .ok()
+        .and_then(|json| serde_json::from_str::<Vec<HubModule>>(&json).ok())
+        .is_some_and(|modules| {
+            modules.iter().any(|m| {
+                m.id.eq_ignore_ascii_case(module_id) && m.is_installed.unwrap_or(m.selected)
+            })
+        })
+}
+
+fn download_and_extract_module(
+    module: &Module<'_>,
+    editor_dir: &Path,
+    destination: &Path,
+    staging_dir: &Path,
+) -> anyhow::Result<()> {
+    let archive = download_verified(module.url, module.integrity, module.name, staging_dir)?;
+
+    let _status = StatusLine::new("Installing", module.name);
+    match module.module_type {
+        "ZIP" => extract_zip(&archive, destination)?,
+        "TAR_XZ" | "TAR_GZ" => extract_tar(&archive, destination)?,
+        "PKG" => {
+            let expand_dir = staging_dir.join("expanded");
+            expand_pkg(&archive, &expand_dir)?;
+            let payload = find_directory(&expand_dir, "Payload")?;
+            fs::create_dir_all(destination)?;
+            for entry in fs::read_dir(payload)?.flatten() {
+                fs::rename(entry.path(), destination.join(entry.file_name()))?;
+            }
+        }
+        "EXE" => run_installer(&archive, destination)?,
+        other => {
+            return Err(anyhow!(
+                "Unsupported module type `{other}` of {}",
+                module.name
+            ));
+        }
+    }
+
+    if let Some(rename) = module.extracted_path_rename {
+        let from = resolve_module_path(&rename.from, editor_dir);
+        let to = resolve_module_path(&rename.to, editor_dir);
+        fs::rename(&from, &to).with_context(|| {
+            format!(
+                "Cannot rename `{}` to `{}`",
+                from.normalized_display(),
+                to.normalized_display()
+            )
+        })?;
+    }
+
+    Ok(())
+}
+
+/// Extracts a zip archive into the given directory.
+fn extract_zip(archive: &Path, dir: &Path) -> anyhow::Result<()> {
+    let file = fs::File::open(archive)?;
+    zip::ZipArchive::new(file)
+        .and_then(|mut zip| zip.extract(dir))
+        .with_context(|| format!("Cannot extract `{}`", archive.normalized_display()))
+}
+
+/// Extracts a (compressed) tar archive into the given directory.
+fn extract_tar(archive: &Path, dir: &Path) -> anyhow::Result<()> {
+    fs::create_dir_all(dir)?;
+    let mut cmd = Command::new("tar");
+    cmd.arg("-xf").arg(archive).arg("-C").arg(dir);
+    run_quiet(cmd).with_context(|| format!("Cannot extract `{}`", archive.normalized_display()))
+}
+
+/// Expands a macOS installer package, including its payload, into the given directory.
+fn expand_pkg(package: &Path, dir: &Path) -> anyhow::Result<()> {
+    let mut cmd = Command::new("pkgutil");
+    cmd.arg("--expand-full").arg(package).arg(dir);
+    run_quiet(cmd).with_context(|| format!("Cannot expand `{}`", package.normalized_display()))
+}
+
+/// Runs a Windows installer silently, installing into the given directory.
+fn run_installer(installer: &Path, dir: &Path) -> anyhow::Result<()> {
+    let mut cmd = Command::new(installer);
+    // The installer requires the destination to be the last argument, without quotes.
+    cmd.arg("/S").arg(format!("/D={}", dir.display()));
+    run_quiet(cmd).with_context(|| format!("Cannot run `{}`", installer.normalized_display()))
+}
+
+/// Returns the first directory with the given name in the directory.
+fn find_directory(dir: &Path, name: &str) -> anyhow::Result<PathBuf> {
+    WalkDir::new(dir)
+        .max_depth(4)
+        .into_iter()
+        .flatten()
+        .find(|e| e.file_type().is_dir() && e.file_name() == name)
+        .map(walkdir::DirEntry::into_path)
+        .ok_or_else(|| anyhow!("Cannot find `{name}` in the expanded package"))
+}
+
+/// Runs the command without output and returns an error if it fails.
+fn run_quiet(mut cmd: Command) -> anyhow::Result<()> {
+    let output = cmd.output()?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(CommandError::from(output).into())
+    }
+}
+
+//
+// Tests
+//
+
+#[cfg(test)]
+mod installer_tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_module_path() {
+        let editor_dir = Path::new("/editors/2022.3.5f1");
+        assert_eq!(
+            resolve_module_path(
+                "{UNITY_PATH}/Editor/Data/PlaybackEngines/AndroidPlayer",
+                editor_dir
+            ),
+            editor_dir.join("Editor/Data/PlaybackEngines/AndroidPlayer")
+        );
+        assert_eq!(
+            resolve_module_path("/opt/elsewhere", editor_dir),
+            PathBuf::from("/opt/elsewhere")
+        );
+    }
+
+    #[test]
+    fn test_is_installed_by_hub() {
+        let editor_dir =
+            std::env::temp_dir().join(format!("ucom-modules-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&editor_dir).unwrap();
+        assert!(!is_installed_by_hub("android", &editor_dir));
+
+        fs::write(
+            editor_dir.join(HUB_MODULES_FILE),
+            r#"[
+                { "id": "android", "name": "Android Build Support", "isInstalled": true },
+                { "id": "ios", "name": "iOS Build Support", "isInstalled": false, "selected": true },
+                { "id": "webgl", "name": "WebGL Build Support", "selected": true },
+                { "id": "linux-il2cpp", "name": "Linux Build Support (IL2CPP)", "selected": false }
+            ]"#,
+        )
+        .unwrap();
+
+        assert!(is_installed_by_hub("android", &editor_dir));
+        assert!(!is_installed_by_hub("ios", &editor_dir));
+        assert!(is_installed_by_hub("webgl", &editor_dir));
+        assert!(!is_installed_by_hub("linux-il2cpp", &editor_dir));
+        assert!(!is_installed_by_hub("windows-mono", &editor_dir));
+
+        fs::remove_dir_all(&editor_dir).unwrap();
+    }
+
+    #[test]
+    fn test_replace_url_host() {
+        assert_eq!(
+            replace_url_host(
+                "https://download.unity3d.com/download_unity/abc/LinuxEditorInstaller/Unity.tar.xz",
+                "http://127.0.0.1:8080/"
+            ),
+            "http://127.0.0.1:8080/download_unity/abc/LinuxEditorInstaller/Unity.tar.xz"
+        );
+    }
+}
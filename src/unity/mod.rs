@@ -4,13 +4,19 @@ pub use crate::unity::version::*;
 pub use crate::utils::spawn_cmd::*;
 
 use anyhow::Result;
-use sysinfo::System;
+use std::path::Path;
+use sysinfo::{Pid, System};
 
+pub mod diagnostics;
+pub mod hub;
 pub mod installations;
+pub mod installer;
 pub mod project;
+pub mod project_settings_file;
 pub mod release_api;
 pub mod release_api_data;
 pub mod releases;
+pub mod transient_failures;
 pub mod version;
 
 /// Checks if Unity Editor is running for the given project.
@@ -18,17 +24,23 @@ pub mod version;
 /// Returns `true` if a Unity Editor process is found with the project loaded.
 /// Unity Hub processes are ignored as they may also reference project paths.
 pub fn is_unity_editor_running(project: &ProjectPath) -> Result<bool> {
+    Ok(find_unity_editor_process(project).is_some())
+}
+
+/// Returns the id of the Unity Editor process that has the given project loaded.
+/// Unity Hub processes are ignored as they may also reference project paths.
+pub fn find_unity_editor_process(project: &ProjectPath) -> Option<Pid> {
     let sys = System::new_all();
     let project_path = project.to_string_lossy();
 
-    for process in sys.processes().values() {
+    for (pid, process) in sys.processes() {
         let cmd_args = process.cmd();
 
         // Skip Unity Hub - it may have -projectPath in its command line
-        if cmd_args
-            .iter()
-            .any(|arg| arg.to_string_lossy().contains("Unity Hub"))
-        {
+        if cmd_args.iter().any(|arg| {
+            let arg = arg.to_string_lossy();
+            arg.contains("Unity Hub") || arg.contains("unityhub")
+        }) {
             continue;
         }
 
@@ -46,9 +58,18 @@ pub fn is_unity_editor_running(project: &ProjectPath) -> Result<bool> {
                 .to_lowercase()
                 .contains("-projectpath")
         }) {
-            return Ok(true);
+            return Some(*pid);
         }
     }
 
-    Ok(false)
+    None
+}
+
+/// Checks if a Unity Editor is running from the given installation directory.
+pub fn is_editor_installation_running(editor_dir: &Path) -> bool {
+    let sys = System::new_all();
+    sys.processes()
+        .values()
+        .filter_map(|process| process.exe())
+        .any(|exe| exe.starts_with(editor_dir))
 }
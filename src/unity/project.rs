@@ -42,6 +42,13 @@ pub struct Manifest {
     pub enable_lock_file: Option<bool>,
 }
 
+impl Manifest {
+    pub fn from_project(project_dir: &Path) -> anyhow::Result<Self> {
+        let file = File::open(project_dir.join("Packages/manifest.json"))?;
+        serde_json::from_reader(BufReader::new(file)).context("Failed to parse packages manifest")
+    }
+}
+
 #[derive(Deserialize, Debug)]
 #[allow(dead_code)]
 pub struct PackagesLock {
@@ -108,9 +115,10 @@ pub enum PackagesAvailability {
 }
 
 impl Packages {
+    const PACKAGES_LOCK_FILE: &str = "Packages/packages-lock.json";
+
     pub fn from_project(project: &ProjectPath) -> anyhow::Result<PackagesAvailability> {
         const MANIFEST_FILE: &str = "Packages/manifest.json";
-        const PACKAGES_LOCK_FILE: &str = "Packages/packages-lock.json";
 
         let manifest_path = project.join(MANIFEST_FILE);
 
@@ -125,14 +133,19 @@ impl Packages {
             return Ok(PackagesAvailability::LockFileDisabled);
         }
 
-        let lock_file_path = project.join(PACKAGES_LOCK_FILE);
-        if !lock_file_path.exists() {
+        if !project.join(Self::PACKAGES_LOCK_FILE).exists() {
             return Ok(PackagesAvailability::NoLockFile);
         }
 
-        let file = File::open(lock_file_path)?;
-        let packages = serde_json::from_reader(BufReader::new(file))?;
-        Ok(PackagesAvailability::Packages(packages))
+        Ok(PackagesAvailability::Packages(Self::from_lock_file(
+            project,
+        )?))
+    }
+
+    /// Reads all direct and indirect dependencies from the lock file of the project.
+    pub fn from_lock_file(project_dir: &Path) -> anyhow::Result<Self> {
+        let file = File::open(project_dir.join(Self::PACKAGES_LOCK_FILE))?;
+        serde_json::from_reader(BufReader::new(file)).context("Failed to parse packages lock file")
     }
 }
 
@@ -208,6 +221,77 @@ fn test_project_settings_deserialization() {
     assert_eq!(settings.bundle_version, "0.1");
 }
 
+/// Returns true if the asset is a Unity 6 build profile.
+///
+/// Unity 6000.0 and 6000.1 leave `m_EditorClassIdentifier` empty for build profiles, so they are
+/// also recognized by the `m_BuildTarget` and `m_PlatformId` keys. Only the YAML header is read,
+/// other assets can be large and don't need to be read in full.
+fn is_build_profile_asset(reader: impl BufRead) -> bool {
+    const HEADER_LINES: usize = 20;
+    const HEADER_BYTES: u64 = 4096;
+
+    let mut has_build_target = false;
+    let mut has_platform_id = false;
+
+    for line in reader
+        .take(HEADER_BYTES)
+        .lines()
+        .take(HEADER_LINES)
+        .map_while(Result::ok)
+    {
+        let line = line.trim_start();
+        if line.starts_with("m_EditorClassIdentifier:")
+            && line.contains("UnityEditor.Build.Profile.BuildProfile")
+        {
+            return true;
+        }
+        has_build_target |= line.starts_with("m_BuildTarget:");
+        has_platform_id |= line.starts_with("m_PlatformId:");
+    }
+
+    has_build_target && has_platform_id
+}
+
+#[test]
+fn test_is_build_profile_asset() {
+    let profile = "MonoBehaviour:\n  m_Name: Android Store\n  \
+                   m_EditorClassIdentifier: UnityEditor.dll::UnityEditor.Build.Profile.BuildProfile\n  \
+                   m_BuildTarget: 13\n";
+    assert!(is_build_profile_asset(profile.as_bytes()));
+
+    let other = "MonoBehaviour:\n  m_Name: Settings\n  m_EditorClassIdentifier: \n";
+    assert!(!is_build_profile_asset(other.as_bytes()));
+
+    // Unity 6000.0 writes an empty class identifier for build profiles.
+    let unity_6000_0 = "%YAML 1.1\n\
+                        %TAG !u! tag:unity3d.com,2011:\n\
+                        --- !u!114 &11400000\n\
+                        MonoBehaviour:\n  \
+                        m_ObjectHideFlags: 0\n  \
+                        m_CorrespondingSourceObject: {fileID: 0}\n  \
+                        m_PrefabInstance: {fileID: 0}\n  \
+                        m_PrefabAsset: {fileID: 0}\n  \
+                        m_GameObject: {fileID: 0}\n  \
+                        m_Enabled: 1\n  \
+                        m_EditorHideFlags: 0\n  \
+                        m_Script: {fileID: 15003, guid: 0000000000000000e000000000000000, type: 0}\n  \
+                        m_Name: Android\n  \
+                        m_EditorClassIdentifier: \n  \
+                        m_AssetVersion: 1\n  \
+                        m_BuildTarget: 13\n  \
+                        m_Subtarget: 0\n  \
+                        m_PlatformId: b9b35072a6f44c2e863f17467ea3dc13\n  \
+                        m_PlatformBuildProfile:\n    \
+                        rid: 200022742090383361\n";
+    assert!(is_build_profile_asset(unity_6000_0.as_bytes()));
+
+    let late = format!(
+        "{}  m_EditorClassIdentifier: UnityEditor.Build.Profile.BuildProfile\n",
+        "  m_Data: 0\n".repeat(100)
+    );
+    assert!(!is_build_profile_asset(late.as_bytes()));
+}
+
 /// Represents a valid path to a Unity project.
 pub struct ProjectPath(PathBuf);
 
@@ -276,27 +360,29 @@ impl ProjectPath {
         }
     }
 
-    /// Checks the project for build profiles.
-    /// TODO: Try to find profiles outside of the default `Assets/Settings/Build Profiles` directory.
+    /// Checks the project for build profiles anywhere in the `Assets` directory.
+    /// The returned paths are relative to the project directory.
     pub fn build_profiles(&self, version: Version) -> anyhow::Result<BuildProfilesStatus> {
         if version.major < 6000 {
             // Build profiles are supported in Unity 6.0 and later.
             return Ok(BuildProfilesStatus::NotSupported);
         }
 
-        let profiles_path = Path::new("Assets/Settings/Build Profiles");
-        let full_path = self.join(profiles_path);
-
-        if !full_path.exists() {
+        let assets_dir = self.join("Assets");
+        if !assets_dir.exists() {
             return Ok(BuildProfilesStatus::NotFound);
         }
 
-        let profiles = full_path
-            .read_dir()?
+        let profiles = WalkDir::new(&assets_dir)
+            .into_iter()
+            .filter_entry(|de| !is_hidden(de))
             .filter_map(Result::ok)
-            .filter(|de| de.file_type().is_ok_and(|ft| ft.is_file()))
+            .filter(|de| de.file_type().is_file())
             .filter(|de| de.path().extension().and_then(|ext| ext.to_str()) == Some("asset"))
-            .map(|de| profiles_path.join(de.file_name()))
+            .filter(|de| {
+                File::open(de.path()).is_ok_and(|f| is_build_profile_asset(BufReader::new(f)))
+            })
+            .filter_map(|de| de.path().strip_prefix(&self.0).ok().map(Path::to_path_buf))
             .sorted_unstable_by(|a, b| {
                 // Compare paths case-insensitively.
                 let a = a.to_string_lossy();
@@ -314,6 +400,56 @@ impl ProjectPath {
         }
     }
 
+    /// Finds the build profile by its path or by its file name without extension (case-insensitive).
+    /// The returned path is relative to the project directory.
+    pub fn find_build_profile(&self, version: Version, profile: &str) -> anyhow::Result<PathBuf> {
+        let profiles = match self.build_profiles(version)? {
+            BuildProfilesStatus::NotSupported => {
+                return Err(anyhow!(
+                    "Build profiles are not supported by Unity {version}, Unity 6 or later is required"
+                ));
+            }
+            BuildProfilesStatus::NotFound => Vec::new(),
+            BuildProfilesStatus::Available(profiles) => profiles,
+        };
+
+        // Match by path, either relative to the project or absolute.
+        let path = Path::new(profile);
+        let relative_path = path.strip_prefix(&self.0).unwrap_or(path);
+        if let Some(found) = profiles
+            .iter()
+            .find(|p| p.as_path() == relative_path || p.normalized_display().to_string() == profile)
+        {
+            return Ok(found.clone());
+        }
+
+        let matches = profiles
+            .iter()
+            .filter(|p| {
+                p.file_stem()
+                    .is_some_and(|stem| stem.to_string_lossy().eq_ignore_ascii_case(profile))
+            })
+            .collect_vec();
+
+        match matches.as_slice() {
+            [found] => Ok((*found).clone()),
+            [] if profiles.is_empty() => Err(anyhow!(
+                "Build profile `{profile}` not found, the project has no build profiles"
+            )),
+            [] => Err(anyhow!(
+                "Build profile `{profile}` not found, available profiles: {}",
+                profiles
+                    .iter()
+                    .map(|p| p.file_stem().unwrap_or_default().to_string_lossy())
+                    .join(", ")
+            )),
+            _ => Err(anyhow!(
+                "Multiple build profiles are named `{profile}`, use the path instead: {}",
+                matches.iter().map(|p| p.normalized_display()).join(", ")
+            )),
+        }
+    }
+
     /// Checks if the directory contains a Unity project.
     fn contains_unity_project(dir: impl AsRef<Path>) -> bool {
         dir.as_ref().join(VERSION_SUB_PATH).exists()
@@ -0,0 +1,202 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, anyhow};
+
+use crate::unity::ProjectPath;
+use crate::utils::path_ext::PlatformConsistentPathExt;
+
+pub const PROJECT_SETTINGS_FILE: &str = "ProjectSettings/ProjectSettings.asset";
+
+/// The indentation of the player settings in `ProjectSettings.asset`.
+const FIELD_INDENT: &str = "  ";
+
+/// The indentation of the values in a per-platform map, e.g. `buildNumber`.
+const PLATFORM_INDENT: &str = "    ";
+
+/// `ProjectSettings.asset` loaded for editing values in place.
+///
+/// The file is non-standard yaml that isn't fully supported by yaml crates, and Unity rewrites it
+/// anyway. Only the lines of the changed values are touched, everything else is written back as is,
+/// including the line endings.
+pub struct ProjectSettingsFile {
+    path: PathBuf,
+    /// The lines of the file, each including its line ending.
+    lines: Vec<String>,
+}
+
+impl ProjectSettingsFile {
+    pub fn open(project: &ProjectPath) -> anyhow::Result<Self> {
+        let path = project.join(PROJECT_SETTINGS_FILE);
+        let content = fs::read_to_string(&path)
+            .with_context(|| format!("Cannot read `{}`", path.normalized_display()))?;
+        Ok(Self::from_content(path, &content))
+    }
+
+    fn from_content(path: PathBuf, content: &str) -> Self {
+        Self {
+            path,
+            lines: content.split_inclusive('\n').map(str::to_string).collect(),
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns the value of a player setting, e.g. `bundleVersion`.
+    pub fn value(&self, key: &str) -> Option<&str> {
+        self.field_index(key).map(|i| parse_line(&self.lines[i]).1)
+    }
+
+    /// Sets the value of an existing player setting.
+    pub fn set_value(&mut self, key: &str, value: &str) -> anyhow::Result<()> {
+        let index = self
+            .field_index(key)
+            .ok_or_else(|| self.missing_key_error(key))?;
+        self.lines[index] = format_line(FIELD_INDENT, key, value, &self.lines[index]);
+        Ok(())
+    }
+
+    /// Returns the value of a platform in a per-platform player setting, e.g. `iPhone` in
+    /// `buildNumber`.
+    pub fn platform_value(&self, key: &str, platform: &str) -> Option<&str> {
+        self.platform_index(key, platform)
+            .map(|i| parse_line(&self.lines[i]).1)
+    }
+
+    /// Sets the value of a platform in a per-platform player setting. The platform is added if the
+    /// setting doesn't have a value for it yet.
+    pub fn set_platform_value(
+        &mut self,
+        key: &str,
+        platform: &str,
+        value: &str,
+    ) -> anyhow::Result<()> {
+        if let Some(index) = self.platform_index(key, platform) {
+            self.lines[index] = format_line(PLATFORM_INDENT, platform, value, &self.lines[index]);
+            return Ok(());
+        }
+
+        let index = self
+            .field_index(key)
+            .ok_or_else(|| self.missing_key_error(key))?;
+
+        // An empty map is written inline, e.g. `buildNumber: {}`.
+        let ending = line_ending(&self.lines[index]).to_string();
+        self.lines[index] = format!("{FIELD_INDENT}{key}:{ending}");
+        self.lines.insert(
+            index + 1,
+            format!("{PLATFORM_INDENT}{platform}: {value}{ending}"),
+        );
+        Ok(())
+    }
+
+    pub fn save(&self) -> anyhow::Result<()> {
+        fs::write(&self.path, self.lines.concat())
+            .with_context(|| format!("Cannot write `{}`", self.path.normalized_display()))
+    }
+
+    fn field_index(&self, key: &str) -> Option<usize> {
+        self.lines.iter().position(|line| {
+            line.strip_prefix(FIELD_INDENT)
+                .filter(|l| !l.starts_with(' '))
+                .is_some_and(|l| parse_line(l).0 == key)
+        })
+    }
+
+    fn platform_index(&self, key: &str, platform: &str) -> Option<usize> {
+        let start = self.field_index(key)? + 1;
+        self.lines[start..]
+            .iter()
+            .take_while(|line| line.starts_with(PLATFORM_INDENT))
+            .position(|line| parse_line(line).0 == platform)
+            .map(|i| start + i)
+    }
+
+    fn missing_key_error(&self, key: &str) -> anyhow::Error {
+        anyhow!(
+            "Could not find `{key}` in `{}`",
+            self.path.normalized_display()
+        )
+    }
+}
+
+/// Returns the trimmed key and value of a `key: value` line.
+fn parse_line(line: &str) -> (&str, &str) {
+    line.split_once(':')
+        .map_or((line.trim(), ""), |(k, v)| (k.trim(), v.trim()))
+}
+
+fn line_ending(line: &str) -> &str {
+    if line.ends_with("\r\n") {
+        "\r\n"
+    } else if line.ends_with('\n') {
+        "\n"
+    } else {
+        ""
+    }
+}
+
+/// Formats a `key: value` line with the line ending of the line it replaces.
+fn format_line(indent: &str, key: &str, value: &str, replaced_line: &str) -> String {
+    format!("{indent}{key}: {value}{}", line_ending(replaced_line))
+}
+
+//
+// Tests
+//
+
+#[cfg(test)]
+mod project_settings_file_tests {
+    use super::*;
+
+    fn test_file() -> ProjectSettingsFile {
+        let data = include_str!("test_data/ProjectSettings.asset");
+        ProjectSettingsFile::from_content(PathBuf::from(PROJECT_SETTINGS_FILE), data)
+    }
+
+    #[test]
+    fn test_read_values() {
+        let settings = test_file();
+        assert_eq!(settings.value("bundleVersion"), Some("0.1"));
+        assert_eq!(settings.value("AndroidBundleVersionCode"), Some("1"));
+        assert_eq!(settings.platform_value("buildNumber", "iPhone"), Some("0"));
+        assert_eq!(settings.value("iPhone"), None);
+    }
+
+    #[test]
+    fn test_set_values_only_changes_those_lines() {
+        let original = include_str!("test_data/ProjectSettings.asset");
+        let mut settings = test_file();
+        settings.set_value("bundleVersion", "1.2.0").unwrap();
+        settings.set_value("AndroidBundleVersionCode", "2").unwrap();
+        settings
+            .set_platform_value("buildNumber", "iPhone", "2")
+            .unwrap();
+
+        let changed = settings.lines.concat();
+        let expected = original
+            .replace("  bundleVersion: 0.1\n", "  bundleVersion: 1.2.0\n")
+            .replace(
+                "  AndroidBundleVersionCode: 1\n",
+                "  AndroidBundleVersionCode: 2\n",
+            )
+            .replace("    iPhone: 0\n", "    iPhone: 2\n");
+        assert_eq!(changed, expected);
+    }
+
+    #[test]
+    fn test_set_platform_value_in_empty_map() {
+        let content = "PlayerSettings:\r\n  buildNumber: {}\r\n  AndroidBundleVersionCode: 1\r\n";
+        let mut settings = ProjectSettingsFile::from_content(PathBuf::new(), content);
+        settings
+            .set_platform_value("buildNumber", "iPhone", "1")
+            .unwrap();
+        assert_eq!(
+            settings.lines.concat(),
+            "PlayerSettings:\r\n  buildNumber:\r\n    iPhone: 1\r\n  AndroidBundleVersionCode: 1\r\n"
+        );
+        assert!(settings.set_value("missingKey", "1").is_err());
+    }
+}
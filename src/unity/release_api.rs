@@ -210,6 +210,25 @@ fn fetch_releases_page(limit: usize, offset: usize) -> anyhow::Result<ReleaseDat
     serde_json::from_reader(body).context("Failed to parse Unity release data")
 }
 
+/// Fetches the release data of a single version from the Unity Release API.
+/// Unlike the cached release data, this includes the download information.
+pub fn fetch_release(version: Version) -> anyhow::Result<ReleaseData> {
+    let body = ureq::get(RELEASES_API_URL)
+        .query("version", version.to_interned_str())
+        .call()
+        .with_context(|| format!("Failed to fetch Unity release data for version {version}"))?
+        .into_body()
+        .into_reader();
+
+    let page: ReleaseDataPage =
+        serde_json::from_reader(body).context("Failed to parse Unity release data")?;
+
+    page.results
+        .into_iter()
+        .find(|r| r.version == version)
+        .ok_or_else(|| anyhow!("Version {version} not found in releases"))
+}
+
 /// Download release information from the Unity Release API.
 /// Because the API is very slow, we minimize the number of requests when looking for new releases
 /// by assuming there were no new releases if all releases in a page are already in the list.
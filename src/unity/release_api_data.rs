@@ -38,9 +38,11 @@ pub struct ReleaseData {
     #[serde(skip_serializing, rename = "recommended")]
     pub recommended: Option<bool>,
 
-    // Not used
-    #[serde(skip, rename = "downloads")]
+    /// Only available when fetched from the API, not stored in the cached release data.
+    #[serde(skip_serializing, default, rename = "downloads")]
     pub downloads: Option<Vec<DownloadsElement>>,
+
+    // Not used
     #[serde(skip, rename = "thirdPartyNotices")]
     pub third_party_notices: Option<Vec<ThirdPartyNoticesElement>>,
 }
@@ -62,9 +64,9 @@ pub struct DownloadsElement {
     pub download_size: SizeUnitValue,
     #[serde(rename = "installedSize")]
     pub installed_size: SizeUnitValue,
-    #[serde(rename = "integrity")]
+    #[serde(default, rename = "integrity")]
     pub integrity: Option<String>,
-    #[serde(rename = "modules")]
+    #[serde(default, rename = "modules")]
     pub modules: Vec<ModulesElement>,
     #[serde(rename = "platform")]
     pub platform: String,
@@ -90,9 +92,11 @@ pub struct ModulesElement {
     pub description: String,
     #[serde(rename = "downloadSize")]
     pub download_size: SizeUnitValue,
-    #[serde(rename = "eula")]
+    #[serde(default, rename = "eula")]
     pub eula: Option<Vec<EulaElement>>,
-    #[serde(rename = "hidden")]
+    #[serde(default, rename = "extractedPathRename")]
+    pub extracted_path_rename: Option<ExtractedPathRename>,
+    #[serde(default, rename = "hidden")]
     pub hidden: bool,
     #[serde(rename = "id")]
     pub id: String,
@@ -100,21 +104,21 @@ pub struct ModulesElement {
     pub installed_size: SizeUnitValue,
     #[serde(rename = "name")]
     pub name: String,
-    #[serde(rename = "preSelected")]
+    #[serde(default, rename = "preSelected")]
     pub pre_selected: bool,
-    #[serde(rename = "required")]
+    #[serde(default, rename = "required")]
     pub required: bool,
     #[serde(rename = "slug")]
     pub slug: String,
-    #[serde(rename = "subModules")]
+    #[serde(default, rename = "subModules")]
     pub sub_modules: Vec<SubModulesElement>,
     #[serde(rename = "type")]
     pub module_type: String, // Renamed due to keyword collision
     #[serde(rename = "url")]
     pub url: String,
-    #[serde(rename = "destination")]
+    #[serde(default, rename = "destination")]
     pub destination: Option<String>,
-    #[serde(rename = "integrity")]
+    #[serde(default, rename = "integrity")]
     pub integrity: Option<String>,
 }
 
@@ -148,31 +152,31 @@ pub struct SubModulesElement {
     pub category: String,
     #[serde(rename = "description")]
     pub description: String,
-    #[serde(rename = "destination")]
+    #[serde(default, rename = "destination")]
     pub destination: Option<String>,
     #[serde(rename = "downloadSize")]
     pub download_size: SizeUnitValue,
-    #[serde(rename = "eula")]
+    #[serde(default, rename = "eula")]
     pub eula: Option<Vec<EulaElement>>,
-    #[serde(rename = "extractedPathRename")]
+    #[serde(default, rename = "extractedPathRename")]
     pub extracted_path_rename: Option<ExtractedPathRename>,
-    #[serde(rename = "hidden")]
+    #[serde(default, rename = "hidden")]
     pub hidden: bool,
     #[serde(rename = "id")]
     pub id: String,
     #[serde(rename = "installedSize")]
     pub installed_size: SizeUnitValue,
-    #[serde(rename = "integrity")]
+    #[serde(default, rename = "integrity")]
     pub integrity: Option<String>,
     #[serde(rename = "name")]
     pub name: String,
-    #[serde(rename = "preSelected")]
+    #[serde(default, rename = "preSelected")]
     pub pre_selected: bool,
-    #[serde(rename = "required")]
+    #[serde(default, rename = "required")]
     pub required: bool,
     #[serde(rename = "slug")]
     pub slug: String,
-    #[serde(rename = "subModules")]
+    #[serde(default, rename = "subModules")]
     pub sub_modules: Vec<SubModulesElement>,
     #[serde(rename = "type")]
     pub module_type: String, // Renamed due to keyword collision
@@ -0,0 +1,152 @@
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+
+use regex::Regex;
+
+/// Log output of failures that are likely to go away when the command is run again.
+static TRANSIENT_FAILURES: LazyLock<Vec<(Regex, &'static str)>> = LazyLock::new(|| {
+    [
+        (
+            r"It looks like another Unity instance is running|Multiple Unity instances cannot open the same project",
+            "another Unity instance has the project open",
+        ),
+        (
+            r"\[Licensing::(Client|Module|IpcConnector)\].*(?i:timed[- ]out|connection|unavailable|code 5\d\d)",
+            "the licensing server could not be reached",
+        ),
+        (
+            r"LicensingClient has failed validation|Access token is unavailable",
+            "the license could not be validated",
+        ),
+        (
+            r"ECONNRESET|ECONNREFUSED|ETIMEDOUT|EAI_AGAIN|ENOTFOUND|socket hang up|Cannot connect to '[^']+'",
+            "package resolution failed because of a network error",
+        ),
+    ]
+    .into_iter()
+    .map(|(pattern, description)| (Regex::new(pattern).unwrap(), description))
+    .collect()
+});
+
+/// Log output of failures that running the command again will not fix, e.g. compiler errors.
+static PERMANENT_FAILURE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"error CS\d{4}|Scripts have compiler errors").unwrap());
+
+/// The part of the Unity Package Manager log that is written during a command.
+///
+/// The Package Manager writes to a log that is shared by all editors, package resolution errors
+/// only show up there.
+pub struct UpmLog {
+    path: Option<PathBuf>,
+    start: u64,
+}
+
+impl UpmLog {
+    /// Marks the current end of the log, only output written after it is taken into account.
+    pub fn mark() -> Self {
+        let path = upm_log_path();
+        let start = path
+            .as_deref()
+            .and_then(|p| p.metadata().ok())
+            .map_or(0, |md| md.len());
+        Self { path, start }
+    }
+
+    /// Returns the output written to the log since it was marked.
+    fn new_output(&self) -> String {
+        let mut output = String::new();
+        if let Some(mut file) = self.path.as_deref().and_then(|p| File::open(p).ok()) {
+            // The log is replaced when it gets too large, it is then read from the start.
+            let start = if file.metadata().is_ok_and(|md| md.len() >= self.start) {
+                self.start
+            } else {
+                0
+            };
+            let mut bytes = Vec::new();
+            if file.seek(SeekFrom::Start(start)).is_ok() && file.read_to_end(&mut bytes).is_ok() {
+                output = String::from_utf8_lossy(&bytes).into_owned();
+            }
+        }
+        output
+    }
+}
+
+/// Returns the location of the Unity Package Manager log.
+fn upm_log_path() -> Option<PathBuf> {
+    if cfg!(target_os = "macos") {
+        dirs::home_dir().map(|dir| dir.join("Library/Logs/Unity/Upm.log"))
+    } else if cfg!(target_os = "windows") {
+        dirs::data_local_dir().map(|dir| dir.join(r"Unity\Editor\upm.log"))
+    } else {
+        dirs::config_dir().map(|dir| dir.join("unity3d/Upm.log"))
+    }
+}
+
+/// Returns a description of the failure if the logs show that it is transient.
+/// Failures that are not known to be transient, or that also have a permanent cause, return `None`.
+pub fn find_transient_failure(log_file: &Path, upm_log: &UpmLog) -> Option<&'static str> {
+    let log = std::fs::read(log_file)
+        .map(|bytes| String::from_utf8_lossy(&bytes).into_owned())
+        .unwrap_or_default();
+    classify_failure(&log, &upm_log.new_output())
+}
+
+fn classify_failure(log: &str, upm_log: &str) -> Option<&'static str> {
+    if PERMANENT_FAILURE.is_match(log) {
+        return None;
+    }
+
+    TRANSIENT_FAILURES
+        .iter()
+        .find(|(pattern, _)| pattern.is_match(log) || pattern.is_match(upm_log))
+        .map(|(_, description)| *description)
+}
+
+//
+// Tests
+//
+
+#[cfg(test)]
+mod transient_failures_tests {
+    use super::*;
+
+    #[test]
+    fn test_classify_failure() {
+        assert_eq!(
+            classify_failure(
+                "Aborting batchmode due to failure:\nIt looks like another Unity instance is running with this project open.",
+                ""
+            ),
+            Some("another Unity instance has the project open")
+        );
+        assert_eq!(
+            classify_failure(
+                "[Licensing::Client] Error: Timed-out after 60.00s, waiting for channel",
+                ""
+            ),
+            Some("the licensing server could not be reached")
+        );
+        assert_eq!(
+            classify_failure(
+                "An error occurred while resolving packages",
+                "[ERROR] Error: read ECONNRESET"
+            ),
+            Some("package resolution failed because of a network error")
+        );
+        assert_eq!(classify_failure("Build failed", ""), None);
+    }
+
+    #[test]
+    fn test_compiler_errors_are_never_transient() {
+        assert_eq!(
+            classify_failure(
+                "Assets/Player.cs(3,5): error CS0103: The name 'x' does not exist\n\
+                 It looks like another Unity instance is running with this project open.",
+                ""
+            ),
+            None
+        );
+    }
+}
@@ -0,0 +1,196 @@
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::path::Path;
+
+use anyhow::{Context, anyhow};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use sha2::{Digest, Sha256, Sha384, Sha512};
+
+use crate::utils::path_ext::PlatformConsistentPathExt;
+
+/// Size of the buffer used for downloading and hashing files.
+const BUFFER_SIZE: usize = 64 * 1024;
+
+/// Downloads the content of the url to the given file.
+/// The callback is called with the number of downloaded bytes and the total size, if known.
+pub fn download_file<F>(url: &str, path: &Path, progress: F) -> anyhow::Result<()>
+where
+    F: Fn(u64, Option<u64>),
+{
+    let response = ureq::get(url)
+        .call()
+        .with_context(|| format!("Failed to download `{url}`"))?;
+
+    let total = response.body().content_length();
+    let mut reader = response.into_body().into_reader();
+    let mut writer = BufWriter::new(
+        File::create(path)
+            .with_context(|| format!("Cannot create file `{}`", path.normalized_display()))?,
+    );
+
+    let mut buffer = vec![0; BUFFER_SIZE];
+    let mut downloaded = 0;
+
+    loop {
+        let count = reader
+            .read(&mut buffer)
+            .with_context(|| format!("Failed to download `{url}`"))?;
+
+        if count == 0 {
+            break;
+        }
+
+        writer.write_all(&buffer[..count])?;
+        downloaded += count as u64;
+        progress(downloaded, total);
+    }
+
+    writer.flush()?;
+
+    if let Some(total) = total
+        && downloaded != total
+    {
+        return Err(anyhow!(
+            "Download of `{url}` is incomplete: received {downloaded} of {total} bytes"
+        ));
+    }
+
+    Ok(())
+}
+
+/// Verifies the file against a Subresource Integrity string (e.g. `sha256-<base64 hash>`).
+/// If the string contains multiple hashes, the file must match one of them.
+pub fn verify_integrity(path: &Path, integrity: &str) -> anyhow::Result<()> {
+    let mut unsupported = Vec::new();
+    let mut mismatched = Vec::new();
+
+    for hash in integrity.split_whitespace() {
+        let Some((algorithm, expected)) = hash.split_once('-') else {
+            unsupported.push(hash);
+            continue;
+        };
+
+        let digest = match algorithm {
+            "sha256" => file_digest::<Sha256>(path)?,
+            "sha384" => file_digest::<Sha384>(path)?,
+            "sha512" => file_digest::<Sha512>(path)?,
+            _ => {
+                unsupported.push(hash);
+                continue;
+            }
+        };
+
+        // Ignore the optional options that can follow the hash, e.g. `sha256-<hash>?opt`.
+        let expected = expected.split('?').next().unwrap_or_default();
+        if STANDARD.encode(digest) == expected {
+            return Ok(());
+        }
+
+        mismatched.push(algorithm);
+    }
+
+    if !mismatched.is_empty() {
+        return Err(anyhow!(
+            "Integrity check failed for `{}`: the {} hash does not match",
+            path.normalized_display(),
+            mismatched.join("/")
+        ));
+    }
+
+    Err(anyhow!(
+        "Cannot verify integrity of `{}`, unsupported integrity value: `{}`",
+        path.normalized_display(),
+        unsupported.join(" ")
+    ))
+}
+
+/// Returns the digest of the file content.
+pub fn file_digest<D: Digest>(path: &Path) -> anyhow::Result<Vec<u8>> {
+    let mut reader = BufReader::new(
+        File::open(path)
+            .with_context(|| format!("Cannot open file `{}`", path.normalized_display()))?,
+    );
+
+    let mut hasher = D::new();
+    let mut buffer = vec![0; BUFFER_SIZE];
+
+    loop {
+        let count = reader.read(&mut buffer)?;
+        if count == 0 {
+            break;
+        }
+        hasher.update(&buffer[..count]);
+    }
+
+    Ok(hasher.finalize().to_vec())
+}
+
+//
+// Tests
+//
+
+#[cfg(test)]
+mod download_tests {
+    use super::*;
+    use std::io::BufRead;
+    use std::net::TcpListener;
+    use std::thread;
+
+    /// Serves the content once on a local port and returns the url to it.
+    fn serve_once(content: &'static [u8]) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+
+        thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(&stream);
+
+            // Read the request headers until the empty line.
+            let mut line = String::new();
+            while reader.read_line(&mut line).unwrap() > 2 {
+                line.clear();
+            }
+
+            let mut writer = &stream;
+            write!(
+                writer,
+                "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                content.len()
+            )
+            .unwrap();
+            writer.write_all(content).unwrap();
+        });
+
+        format!("http://{address}/Unity.tar.xz")
+    }
+
+    #[test]
+    fn test_download_and_verify_integrity() {
+        const CONTENT: &[u8] = b"not really a Unity editor";
+
+        let path = std::env::temp_dir().join(format!("ucom-download-{}", uuid::Uuid::new_v4()));
+        let url = serve_once(CONTENT);
+
+        let last_progress = std::cell::Cell::new((0, None));
+        download_file(&url, &path, |count, total| {
+            last_progress.set((count, total))
+        })
+        .unwrap();
+
+        assert_eq!(std::fs::read(&path).unwrap(), CONTENT);
+        assert_eq!(
+            last_progress.get(),
+            (CONTENT.len() as u64, Some(CONTENT.len() as u64))
+        );
+
+        let integrity = format!("sha256-{}", STANDARD.encode(Sha256::digest(CONTENT)));
+        assert!(verify_integrity(&path, &integrity).is_ok());
+
+        let wrong_integrity = format!("sha256-{}", STANDARD.encode(Sha256::digest(b"other")));
+        assert!(verify_integrity(&path, &wrong_integrity).is_err());
+        assert!(verify_integrity(&path, "md5-abc").is_err());
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}
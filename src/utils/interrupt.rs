@@ -0,0 +1,89 @@
+use std::sync::{Mutex, PoisonError};
+use std::time::Duration;
+
+use anyhow::Context;
+
+use crate::utils::spawn_cmd::terminate_process_tree;
+
+/// The exit code when ucom is interrupted, the same as shells use for Ctrl+C.
+pub const INTERRUPTED_EXIT_CODE: i32 = 130;
+
+/// How long Unity gets to exit after it is asked to, before it is killed.
+const TERMINATE_GRACE_PERIOD: Duration = Duration::from_secs(5);
+
+type Cleanup = Box<dyn FnOnce() + Send>;
+
+/// The running child processes and the cleanups to run when ucom is interrupted.
+struct Registry {
+    next_id: u64,
+    children: Vec<u32>,
+    cleanups: Vec<(u64, Cleanup)>,
+}
+
+static REGISTRY: Mutex<Registry> = Mutex::new(Registry {
+    next_id: 0,
+    children: Vec::new(),
+    cleanups: Vec::new(),
+});
+
+fn registry() -> std::sync::MutexGuard<'static, Registry> {
+    REGISTRY.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Installs the handler for Ctrl+C and termination signals. It stops the running child processes
+/// and runs the registered cleanups before exiting.
+pub fn install_handler() -> anyhow::Result<()> {
+    ctrlc::set_handler(|| {
+        // The lock is held until exiting, which blocks the interrupted command when it tries to
+        // run the cleanups itself.
+        let mut registry = registry();
+
+        for pid in registry.children.drain(..) {
+            terminate_process_tree(pid, TERMINATE_GRACE_PERIOD);
+        }
+
+        for (_, cleanup) in registry.cleanups.drain(..).rev() {
+            cleanup();
+        }
+
+        eprintln!("Interrupted");
+        std::process::exit(INTERRUPTED_EXIT_CODE);
+    })
+    .context("Cannot install the interrupt handler")
+}
+
+/// Stops the child process, and the processes it started, when ucom is interrupted while the guard
+/// is alive.
+pub struct ChildGuard(u32);
+
+impl ChildGuard {
+    pub fn new(pid: u32) -> Self {
+        registry().children.push(pid);
+        Self(pid)
+    }
+}
+
+impl Drop for ChildGuard {
+    fn drop(&mut self) {
+        registry().children.retain(|&pid| pid != self.0);
+    }
+}
+
+/// Runs the cleanup when ucom is interrupted while the guard is alive.
+pub struct CleanupGuard(u64);
+
+impl CleanupGuard {
+    pub fn new(cleanup: impl FnOnce() + Send + 'static) -> Self {
+        let mut registry = registry();
+        let id = registry.next_id;
+        registry.next_id += 1;
+        registry.cleanups.push((id, Box::new(cleanup)));
+        Self(id)
+    }
+}
+
+impl Drop for CleanupGuard {
+    fn drop(&mut self) {
+        registry().cleanups.retain(|(id, _)| *id != self.0);
+    }
+}
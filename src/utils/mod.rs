@@ -2,10 +2,16 @@ use crate::utils::path_ext::PlatformConsistentPathExt;
 use anyhow::{Context, anyhow};
 use path_absolutize::Absolutize;
 use std::borrow::Cow;
+use std::io;
+use std::io::{IsTerminal, Write};
 use std::path::Path;
+use std::time::Duration;
+use walkdir::WalkDir;
 
 pub mod content_cache;
+pub mod download;
 pub mod formatter;
+pub mod interrupt;
 pub mod path_ext;
 pub mod report;
 pub mod spawn_cmd;
@@ -33,3 +39,136 @@ pub fn resolve_absolute_dir_path(path: &impl AsRef<Path>) -> anyhow::Result<Cow<
     let path = path.absolutize().context("Failed to absolutize the path")?;
     Ok(path)
 }
+
+/// Returns the total size in bytes of all files in the directory and its subdirectories.
+pub fn dir_size(path: &Path) -> u64 {
+    WalkDir::new(path)
+        .into_iter()
+        .flatten()
+        .filter(|de| de.file_type().is_file())
+        .filter_map(|de| de.metadata().ok())
+        .map(|md| md.len())
+        .sum()
+}
+
+/// Formats the size in bytes as a human-readable string, e.g. `1.5 GB`.
+pub fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KB", "MB", "GB", "TB"];
+
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+
+    if unit == 0 {
+        format!("{bytes} {}", UNITS[unit])
+    } else {
+        format!("{size:.1} {}", UNITS[unit])
+    }
+}
+
+/// Parses a duration like `90s`, `15m`, `1h30m` or `2h`. A number without a unit is in seconds.
+pub fn parse_duration(value: &str) -> anyhow::Result<Duration> {
+    let invalid = || anyhow!("Invalid duration `{value}`, expected e.g. `90s`, `15m` or `1h30m`");
+
+    let value = value.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Ok(Duration::from_secs(seconds));
+    }
+
+    let mut total = 0;
+    let mut number = String::new();
+    for c in value.chars() {
+        if c.is_ascii_digit() {
+            number.push(c);
+            continue;
+        }
+
+        let multiplier = match c {
+            'h' => 3600,
+            'm' => 60,
+            's' => 1,
+            _ => return Err(invalid()),
+        };
+        let amount: u64 = number.parse().map_err(|_| invalid())?;
+        total += amount * multiplier;
+        number.clear();
+    }
+
+    if !number.is_empty() || value.is_empty() {
+        return Err(invalid());
+    }
+    Ok(Duration::from_secs(total))
+}
+
+/// Formats a duration as parsed by [`parse_duration`], e.g. `1h30m`.
+pub fn format_duration(duration: Duration) -> String {
+    let seconds = duration.as_secs();
+    let (hours, minutes, seconds) = (seconds / 3600, seconds / 60 % 60, seconds % 60);
+
+    let mut formatted = String::new();
+    if hours > 0 {
+        formatted.push_str(&format!("{hours}h"));
+    }
+    if minutes > 0 {
+        formatted.push_str(&format!("{minutes}m"));
+    }
+    if seconds > 0 || formatted.is_empty() {
+        formatted.push_str(&format!("{seconds}s"));
+    }
+    formatted
+}
+
+/// Asks the user to confirm with `y` or `yes`.
+/// Returns an error if there is no terminal to ask the user.
+pub fn confirm(prompt: impl AsRef<str>) -> anyhow::Result<bool> {
+    if !io::stdin().is_terminal() {
+        return Err(anyhow!(
+            "Cannot ask for confirmation without a terminal, use `--yes` to confirm"
+        ));
+    }
+
+    print!("{} [y/N] ", prompt.as_ref());
+    io::stdout().flush()?;
+
+    let mut answer = String::new();
+    io::stdin().read_line(&mut answer)?;
+    Ok(matches!(answer.trim().to_lowercase().as_str(), "y" | "yes"))
+}
+
+//
+// Tests
+//
+
+#[cfg(test)]
+mod utils_tests {
+    use super::*;
+
+    #[test]
+    fn test_format_size() {
+        assert_eq!(format_size(512), "512 B");
+        assert_eq!(format_size(1536), "1.5 KB");
+        assert_eq!(format_size(20 * 1024 * 1024 * 1024), "20.0 GB");
+    }
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("90").unwrap(), Duration::from_secs(90));
+        assert_eq!(parse_duration("90s").unwrap(), Duration::from_secs(90));
+        assert_eq!(parse_duration("15m").unwrap(), Duration::from_secs(900));
+        assert_eq!(parse_duration("1h30m").unwrap(), Duration::from_secs(5400));
+        assert!(parse_duration("").is_err());
+        assert!(parse_duration("10x").is_err());
+        assert!(parse_duration("1h30").is_err());
+        assert!(parse_duration("m").is_err());
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(Duration::from_secs(5400)), "1h30m");
+        assert_eq!(format_duration(Duration::from_secs(61)), "1m1s");
+        assert_eq!(format_duration(Duration::ZERO), "0s");
+    }
+}
@@ -1,13 +1,27 @@
+use std::collections::VecDeque;
 use std::error::Error;
 use std::fmt::{Display, Formatter};
-use std::io::Read;
-use std::path::Path;
-use std::process::{Command, Stdio};
+use std::io::{BufRead, Read};
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, ExitStatus, Stdio};
 use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, Ordering};
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use std::{fs, io, thread};
 
+use sysinfo::{Pid, ProcessesToUpdate, Signal, System};
+
+use crate::utils::{format_duration, interrupt};
+
+/// The exit code when a command is killed because it timed out, the same as the `timeout` utility.
+pub const TIMEOUT_EXIT_CODE: i32 = 124;
+
+/// The number of lines at the end of the log that are reported when a command times out.
+const LOG_TAIL_LINES: usize = 20;
+
+/// How often a running command is checked for timeouts.
+const WATCHDOG_INTERVAL: Duration = Duration::from_millis(200);
+
 //
 // Error implementation
 //
@@ -17,6 +31,25 @@ use std::{fs, io, thread};
 pub struct CommandError {
     pub exit_code: i32,
     pub stderr: String,
+    /// True if the command was killed because it timed out.
+    pub timed_out: bool,
+}
+
+impl CommandError {
+    /// Creates the error for a command that was killed by the watchdog, including the end of its log.
+    fn timeout(reason: &str, log_file: Option<&Path>) -> Self {
+        let mut message = format!("{reason}, the process was killed");
+        if let Some(tail) = log_file.and_then(|log_file| read_log_tail(log_file, LOG_TAIL_LINES)) {
+            message.push_str("\nLast lines of the log:\n");
+            message.push_str(&tail.join("\n"));
+        }
+
+        Self {
+            exit_code: TIMEOUT_EXIT_CODE,
+            stderr: message,
+            timed_out: true,
+        }
+    }
 }
 
 impl Error for CommandError {}
@@ -30,7 +63,11 @@ impl From<std::process::Output> for CommandError {
             String::from_utf8_lossy(e.as_bytes()).into_owned()
         });
 
-        Self { exit_code, stderr }
+        Self {
+            exit_code,
+            stderr,
+            timed_out: false,
+        }
     }
 }
 
@@ -39,12 +76,17 @@ impl From<io::Error> for CommandError {
         Self {
             exit_code: err.raw_os_error().unwrap_or(-1),
             stderr: err.to_string(),
+            timed_out: false,
         }
     }
 }
 
 impl Display for CommandError {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.timed_out {
+            return write!(f, "{}", self.stderr);
+        }
+
         write!(
             f,
             "Command failed with exit code: {} {}",
@@ -73,8 +115,8 @@ pub fn build_command_line(cmd: &Command) -> String {
     for arg in cmd.get_args() {
         line.push(' ');
         let arg = arg.to_string_lossy();
-        // Handle spaces in arguments.
-        if arg.contains(char::is_whitespace) {
+        // Handle spaces and command separators in arguments.
+        if arg.contains(|c: char| c.is_whitespace() || c == ';') {
             line.push('\"');
             line.push_str(&arg);
             line.push('\"');
@@ -85,9 +127,39 @@ pub fn build_command_line(cmd: &Command) -> String {
     line
 }
 
+/// Limits on how long a command may run before it is killed, together with its child processes.
+#[derive(Debug, Default, Clone)]
+pub struct Timeouts {
+    /// The maximum run time.
+    pub total: Option<Duration>,
+    /// The maximum time without new output in the log file.
+    pub idle: Option<Duration>,
+    /// The log file that is watched for new output and of which the last lines are reported.
+    pub log_file: Option<PathBuf>,
+}
+
+impl Timeouts {
+    fn is_set(&self) -> bool {
+        self.total.is_some() || self.idle.is_some()
+    }
+}
+
 /// Spawns command and outputs Unity's log to the console. Blocks until the command has finished.
-pub fn wait_with_log_output(mut cmd: Command, log_file: &Path) -> Result<(), CommandError> {
-    let child = cmd.stdout(Stdio::piped()).stderr(Stdio::piped()).spawn()?;
+pub fn wait_with_log_output(
+    mut cmd: Command,
+    log_file: &Path,
+    timeouts: &Timeouts,
+) -> Result<(), CommandError> {
+    let mut child = cmd.stdout(Stdio::null()).stderr(Stdio::piped()).spawn()?;
+
+    // Read stderr while waiting, so the process doesn't block on a full pipe.
+    let stderr_reader = child.stderr.take().map(|mut stderr| {
+        thread::spawn(move || {
+            let mut buffer = Vec::new();
+            let _ = stderr.read_to_end(&mut buffer);
+            buffer
+        })
+    });
 
     let stop_monitoring = Arc::new(AtomicBool::new(false));
 
@@ -97,21 +169,29 @@ pub fn wait_with_log_output(mut cmd: Command, log_file: &Path) -> Result<(), Com
         move || monitor_log_file(&log_file, Duration::from_millis(100), &stop_monitoring)
     });
 
-    let output = child.wait_with_output();
+    let status = wait_with_timeouts(&mut child, timeouts);
     stop_monitoring.store(true, Ordering::Release);
 
     // Wait for the log monitor thread to finish.
     log_monitor.join().map_err(|e| CommandError {
         exit_code: -1,
         stderr: format!("Echo runner thread panicked: {e:?}"),
+        timed_out: false,
     })??;
 
-    let output = output?;
-    output
-        .status
-        .success()
-        .then_some(())
-        .ok_or_else(|| output.into())
+    let status = status?;
+    let stderr = stderr_reader
+        .and_then(|reader| reader.join().ok())
+        .unwrap_or_default();
+
+    status.success().then_some(()).ok_or_else(|| {
+        std::process::Output {
+            status,
+            stdout: Vec::new(),
+            stderr,
+        }
+        .into()
+    })
 }
 
 /// Spawns command and immediately returns without any output.
@@ -121,19 +201,147 @@ pub fn spawn_and_forget(mut cmd: Command) -> Result<(), CommandError> {
 }
 
 /// Spawns command and outputs to the console. Blocks until the command has finished.
-pub fn wait_with_stdout(mut cmd: Command) -> Result<(), CommandError> {
-    let child = cmd
+pub fn wait_with_stdout(mut cmd: Command, timeouts: &Timeouts) -> Result<(), CommandError> {
+    let mut child = cmd
         .stdout(Stdio::inherit())
         .stderr(Stdio::inherit())
         .spawn()?;
 
-    let output = child.wait_with_output()?;
+    let status = wait_with_timeouts(&mut child, timeouts)?;
+
+    status.success().then_some(()).ok_or_else(|| {
+        std::process::Output {
+            status,
+            stdout: Vec::new(),
+            stderr: Vec::new(),
+        }
+        .into()
+    })
+}
+
+/// Waits for the child to exit. Kills it, and the processes it started, if a timeout expires.
+fn wait_with_timeouts(child: &mut Child, timeouts: &Timeouts) -> Result<ExitStatus, CommandError> {
+    // Stop the process when ucom is interrupted while waiting for it.
+    let _interrupt_guard = interrupt::ChildGuard::new(child.id());
+
+    if !timeouts.is_set() {
+        return Ok(child.wait()?);
+    }
+
+    let start = Instant::now();
+    let mut last_output = start;
+    let mut log_size = None;
+
+    loop {
+        if let Some(status) = child.try_wait()? {
+            return Ok(status);
+        }
+
+        if let Some(log_file) = &timeouts.log_file {
+            let size = fs::metadata(log_file).map(|md| md.len()).ok();
+            if size != log_size {
+                log_size = size;
+                last_output = Instant::now();
+            }
+        }
+
+        let reason = match (timeouts.total, timeouts.idle) {
+            (Some(total), _) if start.elapsed() >= total => {
+                Some(format!("Timed out after {}", format_duration(total)))
+            }
+            (_, Some(idle)) if last_output.elapsed() >= idle => Some(format!(
+                "No new log output for {}, the editor seems to hang",
+                format_duration(idle)
+            )),
+            _ => None,
+        };
+
+        if let Some(reason) = reason {
+            kill_process_tree(child);
+            return Err(CommandError::timeout(&reason, timeouts.log_file.as_deref()));
+        }
+
+        thread::sleep(WATCHDOG_INTERVAL);
+    }
+}
+
+/// Kills the process and all processes started by it, e.g. Unity's shader compilers and build
+/// backend, which would otherwise keep running.
+fn kill_process_tree(child: &mut Child) {
+    let mut system = System::new();
+    system.refresh_processes(ProcessesToUpdate::All, true);
+    let tree = process_tree(&system, Pid::from_u32(child.id()));
+
+    // Kill the parent first, so it cannot start new processes.
+    let _ = child.kill();
+    for pid in tree.iter().skip(1) {
+        if let Some(process) = system.process(*pid) {
+            process.kill();
+        }
+    }
+    let _ = child.wait();
+}
 
-    output
-        .status
-        .success()
-        .then_some(())
-        .ok_or_else(|| output.into())
+/// Asks the process and all processes started by it to exit. Those that are still running after
+/// the grace period are killed.
+pub fn terminate_process_tree(pid: u32, grace_period: Duration) {
+    let mut system = System::new();
+    system.refresh_processes(ProcessesToUpdate::All, true);
+    let tree = process_tree(&system, Pid::from_u32(pid));
+
+    for pid in &tree {
+        if let Some(process) = system.process(*pid) {
+            // Not all platforms support the termination signal.
+            if process.kill_with(Signal::Term) != Some(true) {
+                process.kill();
+            }
+        }
+    }
+
+    let start = Instant::now();
+    while start.elapsed() < grace_period {
+        system.refresh_processes(ProcessesToUpdate::Some(&tree), true);
+        if tree.iter().all(|pid| system.process(*pid).is_none()) {
+            return;
+        }
+        thread::sleep(WATCHDOG_INTERVAL);
+    }
+
+    for pid in &tree {
+        if let Some(process) = system.process(*pid) {
+            process.kill();
+        }
+    }
+}
+
+/// Returns the process and all its descendants, parents before their children.
+fn process_tree(system: &System, root: Pid) -> Vec<Pid> {
+    let mut tree = vec![root];
+    let mut index = 0;
+    while let Some(&parent) = tree.get(index) {
+        tree.extend(
+            system
+                .processes()
+                .iter()
+                .filter(|(_, process)| process.parent() == Some(parent))
+                .map(|(&pid, _)| pid),
+        );
+        index += 1;
+    }
+    tree
+}
+
+/// Returns the last lines of the log file.
+fn read_log_tail(log_file: &Path, count: usize) -> Option<Vec<String>> {
+    let file = fs::File::open(log_file).ok()?;
+    let mut tail = VecDeque::with_capacity(count + 1);
+    for line in io::BufReader::new(file).lines().map_while(Result::ok) {
+        tail.push_back(line);
+        if tail.len() > count {
+            tail.pop_front();
+        }
+    }
+    (!tail.is_empty()).then(|| tail.into())
 }
 
 /// Continuously reads the log file and prints it to the console.
@@ -16,6 +16,8 @@
  * limitations under the License.
  */
 
+// ucom-template-version: 1
+
 #if UNITY_2018_3_OR_NEWER
 using System.Collections.Generic;
 using System.Linq;
@@ -16,6 +16,10 @@
  * limitations under the License.
  */
 
+// ucom-template-version: 5
+// ucom compares this version with the version of its own template, it is increased when the command line
+// arguments or the editor IPC protocol change. Update the file with 'ucom add builder --update'.
+
 #if UNITY_2018_3_OR_NEWER
 using System;
 using System.Collections.Generic;
@@ -25,10 +29,21 @@ using System.IO;
 using System.Linq;
 using System.Reflection;
 using System.Text;
+using System.Text.RegularExpressions;
+using System.Xml;
 using JetBrains.Annotations;
 using UnityEditor;
+using UnityEditor.Build;
 using UnityEditor.Build.Reporting;
+using UnityEditor.SceneManagement;
+#if !UCOM_NO_TEST_FRAMEWORK
+using UnityEditor.TestTools.TestRunner.Api;
+#endif
+#if UNITY_6000_0_OR_NEWER
+using UnityEditor.Build.Profile;
+#endif
 using UnityEngine;
+using UnityEngine.SceneManagement;
 using Debug = UnityEngine.Debug;
 
 // ReSharper disable SwitchStatementHandlesSomeKnownEnumValuesWithDefault
@@ -53,6 +68,11 @@ namespace Ucom
         /// </summary>
         private const string BuildTargetArg = "--ucom-build-target";
 
+        /// <summary>
+        /// The project relative path of the build profile asset to build. Replaces the build target.
+        /// </summary>
+        private const string BuildProfileArg = "--ucom-build-profile";
+
         /// <summary>
         /// Combined <see cref="BuildOptions"/> are passed as an int.
         /// </summary>
@@ -68,13 +88,25 @@ namespace Ucom
         /// </summary>
         private const string AddDefinesArg = "--ucom-add-defines";
 
+        /// <summary>
+        /// The arguments that ucom sent with a build in an already open editor, <c>null</c> otherwise.
+        /// </summary>
+        internal static string[] EditorBuildArgs { get; set; }
+
+        /// <summary>
+        /// Returns the arguments of the build: the arguments that ucom sent to an already open editor, or the
+        /// command line of Unity. Custom build functions should read their arguments with this method, so they
+        /// work in both cases.
+        /// </summary>
+        public static string[] GetCommandLineArgs() => EditorBuildArgs ?? Environment.GetCommandLineArgs();
+
         /// <summary>
         /// This method is called by ucom to build the project.
         /// </summary>
         [UsedImplicitly]
         public static void Build()
         {
-            var args = Environment.GetCommandLineArgs();
+            var args = GetCommandLineArgs();
 
             var invalidArgs = false;
 
@@ -86,8 +118,27 @@ namespace Ucom
                 invalidArgs = true;
             }
 
+#if UNITY_6000_0_OR_NEWER
+            BuildProfile buildProfile = null;
+#endif
+            string argValue = null;
+
+            if (args.TryGetArgValue(BuildProfileArg, out var profilePath))
+            {
+                // The build profile determines the build target.
+#if UNITY_6000_0_OR_NEWER
+                if (!TryActivateBuildProfile(profilePath, out buildProfile, out var profileError))
+                {
+                    Log($"[Builder] Error: {profileError}", LogType.Error);
+                    invalidArgs = true;
+                }
+#else
+                Log($"[Builder] Error: Build profile '{profilePath}' requires Unity 6 or newer.", LogType.Error);
+                invalidArgs = true;
+#endif
+            }
             // Get the build target.
-            if (!args.TryGetArgValue(BuildTargetArg, out var argValue))
+            else if (!args.TryGetArgValue(BuildTargetArg, out argValue))
             {
                 // No build target specified.
                 Log($"[Builder] Error: Build target '{BuildTargetArg} <target>' not specified.", LogType.Error);
@@ -142,8 +193,13 @@ namespace Ucom
                 out var locationPathName
             );
 
-            buildFailed |= invalidArgs
-                           || !Build(locationPathName, GetActiveScenes(), options, extraScriptingDefines, preBuildArgs);
+#if UNITY_6000_0_OR_NEWER
+            if (buildProfile != null)
+                buildFailed |= invalidArgs || !Build(locationPathName, buildProfile, options, preBuildArgs);
+            else
+#endif
+                buildFailed |= invalidArgs
+                               || !Build(locationPathName, GetActiveScenes(), options, extraScriptingDefines, preBuildArgs);
 
             if (Array.IndexOf(args, "-quit") != -1)
             {
@@ -185,11 +241,72 @@ namespace Ucom
             if (!RunPreProcessBuildMethod(preBuildArgs))
                 return false;
 
+            return BuildPlayer(() => BuildPipeline.BuildPlayer(buildPlayerOptions));
+        }
+
+#if UNITY_6000_0_OR_NEWER
+        /// <summary>
+        /// Builds the application with the scenes, platform and settings of the build profile.
+        /// </summary>
+        /// <param name="locationPathName">The path where the application will be built.</param>
+        /// <param name="buildProfile">The build profile to build.</param>
+        /// <param name="options">Building options. Multiple options can be combined together.</param>
+        /// <param name="preBuildArgs">The pre-build arguments that are passed to methods with the <see cref="UcomPreProcessBuildAttribute"/>.</param>
+        /// <returns><c>true</c> if the build succeeded; <c>false</c> otherwise.</returns>
+        public static bool Build(string locationPathName,
+            BuildProfile buildProfile,
+            BuildOptions options = BuildOptions.None,
+            string preBuildArgs = "")
+        {
+            var buildPlayerOptions = new BuildPlayerWithProfileOptions
+            {
+                buildProfile = buildProfile,
+                locationPathName = locationPathName,
+                options = options,
+            };
+
+            if (!RunPreProcessBuildMethod(preBuildArgs))
+                return false;
+
+            return BuildPlayer(() => BuildPipeline.BuildPlayer(buildPlayerOptions));
+        }
+
+        /// <summary>
+        /// Loads the build profile asset and makes it the active build profile.
+        /// </summary>
+        /// <param name="profilePath">The project relative path of the build profile asset.</param>
+        /// <param name="buildProfile">The loaded build profile.</param>
+        /// <param name="error">The error message if the profile could not be activated.</param>
+        /// <returns><c>true</c> if the build profile is active; <c>false</c> otherwise.</returns>
+        public static bool TryActivateBuildProfile(string profilePath, out BuildProfile buildProfile, out string error)
+        {
+            buildProfile = AssetDatabase.LoadAssetAtPath<BuildProfile>(profilePath);
+            if (buildProfile == null)
+            {
+                error = $"Build profile '{profilePath}' not found.";
+                return false;
+            }
+
+            if (BuildProfile.GetActiveBuildProfile() != buildProfile)
+                BuildProfile.SetActiveBuildProfile(buildProfile);
+
+            error = null;
+            return true;
+        }
+#endif
+
+        /// <summary>
+        /// Runs the build and logs the build report.
+        /// </summary>
+        /// <param name="buildPlayer">Builds the player and returns the report.</param>
+        /// <returns><c>true</c> if the build succeeded; <c>false</c> otherwise.</returns>
+        private static bool BuildPlayer(Func<BuildReport> buildPlayer)
+        {
             BuildReport report;
 
             try
             {
-                report = BuildPipeline.BuildPlayer(buildPlayerOptions);
+                report = buildPlayer();
             }
             catch (Exception e)
             {
@@ -212,7 +329,7 @@ namespace Ucom
                 .AppendLine($"    Errors:       {summary.totalErrors}")
                 .AppendLine($"    Warnings:     {summary.totalWarnings}");
 
-            if (Environment.GetCommandLineArgs().TryGetArgValue("-logFile", out var logFile))
+            if (GetCommandLineArgs().TryGetArgValue("-logFile", out var logFile))
                 sb.AppendLine($"    Log file:     {logFile}");
 
             // End of build report.
@@ -431,7 +548,7 @@ namespace Ucom
             return true;
         }
 
-        private static bool RunPreProcessBuildMethod(string arg)
+        internal static bool RunPreProcessBuildMethod(string arg)
         {
             var methods = AppDomain.CurrentDomain
                 .GetAssemblies()
@@ -532,6 +649,29 @@ namespace Ucom
         private static double _lastPollTime;
         private const double PollIntervalSeconds = 1.0;
 
+        // The heartbeat tells ucom the editor is still alive. It is written from a background thread,
+        // because the main thread is blocked while building.
+        private static readonly string HeartbeatPath;
+        private static System.Threading.Timer _heartbeatTimer;
+        private const int HeartbeatIntervalMilliseconds = 2000;
+
+        // The test command that is running, kept in the session state to survive domain reloads.
+        private const string PendingTestCommandKey = "Ucom.PendingTestCommand";
+
+        // The progress file of the running build, which ucom tails to show the progress. The messages are
+        // collected from the log, which may be written from other threads.
+        private static readonly object ProgressLock = new object();
+        private static string _progressPath;
+        private static List<ResultMessage> _buildMessages;
+        private static int _processedScenes;
+        private static StreamWriter _logWriter;
+
+        private const string DefaultBuildFunction = "Ucom.UnityBuilder.Build";
+
+        private static readonly Regex CompilerMessageRegex = new Regex(
+            @"^(?<file>.+)\((?<line>\d+),(?<column>\d+)\): (?<severity>error|warning) (?<code>\w+): (?<message>.*)$"
+        );
+
         // Deferred command handling for play mode exit and compilation waiting
         private static CommandFile _deferredCommand;
         private static bool _waitingForPlayModeExit;
@@ -541,18 +681,27 @@ namespace Ucom
         {
             CommandDir = Path.Combine(Application.dataPath, "..", "Temp", "ucom-commands");
             ResultDir = Path.Combine(Application.dataPath, "..", "Temp", "ucom-results");
+            HeartbeatPath = Path.Combine(ResultDir, "heartbeat");
 
             Directory.CreateDirectory(CommandDir);
             Directory.CreateDirectory(ResultDir);
 
             InitializeWatcher();
 
+            _heartbeatTimer = new System.Threading.Timer(_ => WriteHeartbeat(), null, 0, HeartbeatIntervalMilliseconds);
+            AssemblyReloadEvents.beforeAssemblyReload += () => _heartbeatTimer?.Dispose();
+
             // Also check for existing commands on load
             CheckForCommands();
 
-            // Cleanup old command files (older than 1 hour)
+            // Cleanup old command and result files (older than 1 hour)
             CleanupOldFiles();
 
+#if !UCOM_NO_TEST_FRAMEWORK
+            // Callbacks don't survive a domain reload, e.g. when entering play mode for play mode tests.
+            ScriptableObject.CreateInstance<TestRunnerApi>().RegisterCallbacks(new TestRunCallbacks());
+#endif
+
             // Register polling callback that works even when Unity is in background
             EditorApplication.update += PollForCommands;
             _lastPollTime = EditorApplication.timeSinceStartup;
@@ -570,6 +719,8 @@ namespace Ucom
 
                 _watcher.Created += OnCommandFileCreated;
                 _watcher.Changed += OnCommandFileCreated;
+                // ucom writes a temporary file and renames it to the command file.
+                _watcher.Renamed += OnCommandFileCreated;
             }
             catch (Exception e)
             {
@@ -639,16 +790,20 @@ namespace Ucom
         {
             try
             {
-                if (!Directory.Exists(CommandDir))
-                    return;
-
                 var cutoff = DateTime.UtcNow.AddHours(-1);
 
-                foreach (var file in Directory.GetFiles(CommandDir, "*.json"))
+                foreach (var dir in new[] { CommandDir, ResultDir })
                 {
-                    if (File.GetLastWriteTimeUtc(file) < cutoff)
+                    if (!Directory.Exists(dir))
+                        continue;
+
+                    var files = Directory.GetFiles(dir, "*.json").Concat(Directory.GetFiles(dir, "*.jsonl"));
+                    foreach (var file in files)
                     {
-                        File.Delete(file);
+                        if (File.GetLastWriteTimeUtc(file) < cutoff)
+                        {
+                            File.Delete(file);
+                        }
                     }
                 }
             }
@@ -658,6 +813,18 @@ namespace Ucom
             }
         }
 
+        private static void WriteHeartbeat()
+        {
+            try
+            {
+                File.WriteAllText(HeartbeatPath, DateTime.UtcNow.ToString("o", CultureInfo.InvariantCulture));
+            }
+            catch (Exception)
+            {
+                // The next heartbeat will try again.
+            }
+        }
+
         private static void ProcessCommandFile(string commandFilePath)
         {
             if (!File.Exists(commandFilePath))
@@ -673,8 +840,30 @@ namespace Ucom
                     case "build":
                         ProcessBuildCommand(command);
                         break;
+                    case "refresh":
+                        ProcessRefreshCommand(command);
+                        break;
+                    case "switch-platform":
+                        ProcessSwitchPlatformCommand(command);
+                        break;
+                    case "execute-method":
+                        ProcessExecuteMethodCommand(command);
+                        break;
+                    case "enter-playmode":
+                    case "exit-playmode":
+                        ProcessPlayModeCommand(command, command.command == "enter-playmode");
+                        break;
+                    case "save-scenes":
+                        ProcessSaveScenesCommand(command);
+                        break;
+                    case "test":
+                        ProcessTestCommand(command);
+                        break;
+                    case "cancel":
+                        CancelCommand(command.uuid);
+                        break;
                     default:
-                        WriteErrorResult(command.uuid,
+                        WriteErrorResult(command,
                             "UNKNOWN_COMMAND",
                             $"Unknown command: {command.command}"
                         );
@@ -690,6 +879,36 @@ namespace Ucom
             }
         }
 
+        /// <summary>
+        /// Cancels a command that ucom no longer waits for, e.g. because it was interrupted or timed out.
+        /// A build or test run that is already running cannot be aborted, its result is discarded.
+        /// </summary>
+        private static void CancelCommand(string uuid)
+        {
+            if (_deferredCommand != null && _deferredCommand.uuid == uuid)
+            {
+                _deferredCommand = null;
+                _waitingForPlayModeExit = false;
+                _waitingForCompilation = false;
+            }
+
+            var pendingTestCommand = SessionState.GetString(PendingTestCommandKey, null);
+            if (!string.IsNullOrEmpty(pendingTestCommand)
+                && JsonUtility.FromJson<CommandFile>(pendingTestCommand).uuid == uuid)
+            {
+                SessionState.EraseString(PendingTestCommandKey);
+            }
+
+            var resultPaths = Directory.GetFiles(ResultDir, $"*-{uuid}.json")
+                .Concat(Directory.GetFiles(ResultDir, $"*-{uuid}.jsonl"));
+            foreach (var resultPath in resultPaths)
+            {
+                File.Delete(resultPath);
+            }
+
+            Debug.Log($"[Ucom] Cancelled command {uuid}");
+        }
+
         private static void ProcessBuildCommand(CommandFile cmd)
         {
             // Validate: Check if in play mode
@@ -697,7 +916,7 @@ namespace Ucom
             {
                 if (!cmd.force_play_mode_exit)
                 {
-                    WriteErrorResult(cmd.uuid,
+                    WriteErrorResult(cmd,
                         "IN_PLAY_MODE",
                         "Unity editor is in Play Mode. Use --force-editor-build to exit play mode."
                     );
@@ -716,7 +935,7 @@ namespace Ucom
             {
                 if (!cmd.force_platform_switch)
                 {
-                    WriteErrorResult(cmd.uuid,
+                    WriteErrorResult(cmd,
                         "COMPILING",
                         "Unity editor is compiling. Use --force-editor-build to wait for compilation."
                     );
@@ -729,10 +948,16 @@ namespace Ucom
                 return; // Will be processed in next poll cycle after compilation finishes
             }
 
+            if (!string.IsNullOrEmpty(cmd.build_profile))
+            {
+                ProcessBuildProfileCommand(cmd);
+                return;
+            }
+
             // Validate: Check platform match
             if (!Enum.TryParse(cmd.platform, out BuildTarget target))
             {
-                WriteErrorResult(cmd.uuid,
+                WriteErrorResult(cmd,
                     "INVALID_PLATFORM",
                     $"Invalid build platform: {cmd.platform}"
                 );
@@ -744,7 +969,7 @@ namespace Ucom
             if (needsPlatformSwitch && !cmd.force_platform_switch)
             {
                 // Platform mismatch without permission to switch
-                WriteErrorResult(cmd.uuid,
+                WriteErrorResult(cmd,
                     "PLATFORM_MISMATCH",
                     $"Editor is in {EditorUserBuildSettings.activeBuildTarget} mode, " +
                     $"but build target is {target}. Use --force-editor-build to switch platforms."
@@ -756,6 +981,39 @@ namespace Ucom
             ExecuteBuild(cmd, target, needsPlatformSwitch);
         }
 
+        private static void ProcessBuildProfileCommand(CommandFile cmd)
+        {
+#if UNITY_6000_0_OR_NEWER
+            var buildProfile = AssetDatabase.LoadAssetAtPath<BuildProfile>(cmd.build_profile);
+            if (buildProfile == null)
+            {
+                WriteErrorResult(cmd,
+                    "INVALID_PROFILE",
+                    $"Build profile not found: {cmd.build_profile}"
+                );
+                return;
+            }
+
+            if (BuildProfile.GetActiveBuildProfile() != buildProfile && !cmd.force_platform_switch)
+            {
+                WriteErrorResult(cmd,
+                    "PROFILE_MISMATCH",
+                    $"Build profile {cmd.build_profile} is not active. Use --force-editor-build to activate it."
+                );
+                return;
+            }
+
+            // Activating the profile switches to its platform.
+            UnityBuilder.TryActivateBuildProfile(cmd.build_profile, out _, out _);
+            ExecuteBuild(cmd, EditorUserBuildSettings.activeBuildTarget, false);
+#else
+            WriteErrorResult(cmd,
+                "PROFILE_NOT_SUPPORTED",
+                "Build profiles require Unity 6 or newer."
+            );
+#endif
+        }
+
         private static bool TrySwitchPlatform(BuildTarget target, out string errorMessage, out float switchTime)
         {
             switchTime = 0f;
@@ -792,11 +1050,33 @@ namespace Ucom
         }
 
         private static void ExecuteBuild(CommandFile cmd, BuildTarget target, bool needsSwitch)
+        {
+            StartProgress(cmd);
+            try
+            {
+                var result = RunBuild(cmd, target, needsSwitch);
+                ReportPhase("Finished", 100f);
+
+                lock (ProgressLock)
+                {
+                    result.messages = new List<ResultMessage>(_buildMessages);
+                }
+
+                WriteResult(result);
+            }
+            finally
+            {
+                StopProgress();
+            }
+        }
+
+        private static ResultFile RunBuild(CommandFile cmd, BuildTarget target, bool needsSwitch)
         {
             var originalPlatform = EditorUserBuildSettings.activeBuildTarget.ToString();
 
             var result = new ResultFile
             {
+                command = cmd.command,
                 uuid = cmd.uuid,
                 original_platform = originalPlatform
             };
@@ -804,13 +1084,13 @@ namespace Ucom
             // Handle platform switch if needed
             if (needsSwitch)
             {
+                ReportPhase("Switching platform");
                 if (!TrySwitchPlatform(target, out var switchError, out var switchTime))
                 {
                     result.status = "failed";
                     result.error_code = "PLATFORM_SWITCH_FAILED";
                     result.message = switchError;
-                    WriteResult(result);
-                    return;
+                    return result;
                 }
 
                 result.platform_switched = true;
@@ -818,6 +1098,11 @@ namespace Ucom
                 result.platform_switch_time_seconds = switchTime;
             }
 
+            if (!string.IsNullOrEmpty(cmd.build_function) && cmd.build_function != DefaultBuildFunction)
+            {
+                return RunBuildFunction(cmd, result);
+            }
+
             // Get the full build location path (directory + filename) using UnityBuilder
             if (!UnityBuilder.TryGetBuildLocationPath(cmd.output_path,
                     Application.productName,
@@ -828,33 +1113,31 @@ namespace Ucom
                 result.status = "failed";
                 result.error_code = "BUILD_FAILED";
                 result.message = $"Build target '{target}' is not supported for building.";
-                WriteResult(result);
-                return;
+                return result;
             }
 
+            ReportPhase("Preparing build");
             var buildStartTime = EditorApplication.timeSinceStartup;
 
-            // Build the player using BuildPipeline to get BuildReport
-            var buildPlayerOptions = new BuildPlayerOptions
-            {
-                scenes = UnityBuilder.GetActiveScenes(),
-                locationPathName = locationPathName,
-                target = target,
-                options = (BuildOptions)cmd.build_options
-            };
-
             BuildReport report;
             try
             {
-                report = BuildPipeline.BuildPlayer(buildPlayerOptions);
+                if (!UnityBuilder.RunPreProcessBuildMethod(cmd.build_args ?? ""))
+                {
+                    result.status = "failed";
+                    result.error_code = "PRE_BUILD_FAILED";
+                    result.message = $"The {nameof(UcomPreProcessBuildAttribute)} method failed.";
+                    return result;
+                }
+
+                report = BuildPlayer(cmd, target, locationPathName);
             }
             catch (Exception e)
             {
                 result.status = "failed";
                 result.error_code = "BUILD_FAILED";
                 result.message = $"Build exception: {e.Message}";
-                WriteResult(result);
-                return;
+                return result;
             }
 
             result.build_time_seconds =
@@ -881,14 +1164,518 @@ namespace Ucom
                 result.message = "Build failed. Check Unity console for errors.";
             }
 
-            WriteResult(result);
+            return result;
+        }
+
+        private static void StartProgress(CommandFile cmd)
+        {
+            lock (ProgressLock)
+            {
+                _progressPath = Path.Combine(ResultDir, $"{cmd.command}-{cmd.uuid}.jsonl");
+                _buildMessages = new List<ResultMessage>();
+                _processedScenes = 0;
+
+                if (!string.IsNullOrEmpty(cmd.log_path))
+                {
+                    try
+                    {
+                        Directory.CreateDirectory(Path.GetDirectoryName(Path.GetFullPath(cmd.log_path)) ?? ".");
+                        _logWriter = new StreamWriter(cmd.log_path, false) { AutoFlush = true };
+                    }
+                    catch (Exception e)
+                    {
+                        Debug.LogWarning($"[Ucom] Failed to create the build log {cmd.log_path}: {e.Message}");
+                    }
+                }
+            }
+
+            UnityBuilder.EditorBuildArgs = cmd.args ?? Array.Empty<string>();
+            Application.logMessageReceivedThreaded += OnLogMessageReceived;
+        }
+
+        private static void StopProgress()
+        {
+            Application.logMessageReceivedThreaded -= OnLogMessageReceived;
+            UnityBuilder.EditorBuildArgs = null;
+
+            lock (ProgressLock)
+            {
+                _progressPath = null;
+                _buildMessages = null;
+                _logWriter?.Dispose();
+                _logWriter = null;
+            }
+        }
+
+        /// <summary>
+        /// Appends a phase of the running build to its progress file, does nothing when no build of ucom
+        /// is running. A negative percentage means the progress within the phase is unknown.
+        /// </summary>
+        internal static void ReportPhase(string phase, float percentage = -1f)
+        {
+            AppendProgressEvent(new ProgressEvent { kind = "phase", phase = phase, percentage = percentage });
+        }
+
+        /// <summary>
+        /// Reports the progress of the scenes that are processed, one call per scene.
+        /// </summary>
+        internal static void ReportSceneProcessed(Scene scene)
+        {
+            int processed;
+            lock (ProgressLock)
+            {
+                processed = ++_processedScenes;
+            }
+
+            var sceneCount = EditorBuildSettings.scenes.Count(s => s.enabled);
+            ReportPhase($"Processing scene {scene.name}",
+                processed <= sceneCount ? 100f * processed / sceneCount : -1f
+            );
+        }
+
+        /// <summary>
+        /// Writes the log of the build to the log file and collects its compiler messages and errors.
+        /// </summary>
+        private static void OnLogMessageReceived(string condition, string stackTrace, LogType type)
+        {
+            lock (ProgressLock)
+            {
+                try
+                {
+                    _logWriter?.WriteLine(condition);
+                    if (!string.IsNullOrEmpty(stackTrace))
+                        _logWriter?.WriteLine(stackTrace);
+                }
+                catch (Exception)
+                {
+                    // The log file is only informational, the result contains all messages.
+                }
+            }
+
+            var message = ParseLogMessage(condition, type);
+            if (message == null)
+                return;
+
+            lock (ProgressLock)
+            {
+                // Unity logs compiler messages more than once.
+                if (_buildMessages == null || _buildMessages.Any(m => m.Equals(message)))
+                    return;
+
+                _buildMessages.Add(message);
+            }
+
+            AppendProgressEvent(ProgressEvent.ForMessage(message));
         }
 
-        private static void WriteErrorResult(string uuid, string errorCode, string message)
+        private static ResultMessage ParseLogMessage(string condition, LogType type)
+        {
+            var match = CompilerMessageRegex.Match(condition.Trim());
+            if (match.Success)
+            {
+                return new ResultMessage
+                {
+                    severity = match.Groups["severity"].Value,
+                    message = match.Groups["message"].Value,
+                    code = match.Groups["code"].Value,
+                    file = match.Groups["file"].Value,
+                    line = int.Parse(match.Groups["line"].Value, CultureInfo.InvariantCulture),
+                    column = int.Parse(match.Groups["column"].Value, CultureInfo.InvariantCulture)
+                };
+            }
+
+            // Other warnings are too noisy, only the compiler warnings are reported.
+            if (type != LogType.Error && type != LogType.Exception && type != LogType.Assert)
+                return null;
+
+            return new ResultMessage { severity = "error", message = condition.Trim() };
+        }
+
+        private static void AppendProgressEvent(ProgressEvent progressEvent)
+        {
+            lock (ProgressLock)
+            {
+                if (_progressPath == null)
+                    return;
+
+                try
+                {
+                    File.AppendAllText(_progressPath, JsonUtility.ToJson(progressEvent) + "\n");
+                }
+                catch (Exception)
+                {
+                    // Progress is only informational, the result contains all messages.
+                }
+            }
+        }
+
+        /// <summary>
+        /// Runs a custom build function like Unity's <c>-executeMethod</c> would. The build fails when the
+        /// function throws or returns <c>false</c>.
+        /// </summary>
+        private static ResultFile RunBuildFunction(CommandFile cmd, ResultFile result)
+        {
+            result.output_path = cmd.output_path;
+
+            var method = FindStaticMethod(cmd.build_function);
+            if (method == null)
+            {
+                result.status = "failed";
+                result.error_code = "METHOD_NOT_FOUND";
+                result.message =
+                    $"Static build function {cmd.build_function} without parameters or with a string[] parameter not found.";
+                return result;
+            }
+
+            ReportPhase($"Running {cmd.build_function}");
+            var buildStartTime = EditorApplication.timeSinceStartup;
+
+            object returnValue;
+            try
+            {
+                returnValue = method.Invoke(null,
+                    method.GetParameters().Length == 0 ? null : new object[] { UnityBuilder.GetCommandLineArgs() }
+                );
+            }
+            catch (TargetInvocationException e)
+            {
+                result.status = "failed";
+                result.error_code = "BUILD_FAILED";
+                result.message = $"{cmd.build_function} failed: {e.InnerException?.Message ?? e.Message}";
+                return result;
+            }
+
+            result.build_time_seconds = (float)(EditorApplication.timeSinceStartup - buildStartTime);
+
+            if (returnValue is bool succeeded && !succeeded)
+            {
+                result.status = "failed";
+                result.error_code = "BUILD_FAILED";
+                result.message = $"{cmd.build_function} returned false.";
+                return result;
+            }
+
+            result.status = "success";
+            result.message = "Build completed successfully";
+            return result;
+        }
+
+        /// <summary>
+        /// Builds the player using BuildPipeline to get the BuildReport.
+        /// </summary>
+        private static BuildReport BuildPlayer(CommandFile cmd, BuildTarget target, string locationPathName)
+        {
+#if UNITY_6000_0_OR_NEWER
+            if (!string.IsNullOrEmpty(cmd.build_profile))
+            {
+                return BuildPipeline.BuildPlayer(new BuildPlayerWithProfileOptions
+                    {
+                        buildProfile = AssetDatabase.LoadAssetAtPath<BuildProfile>(cmd.build_profile),
+                        locationPathName = locationPathName,
+                        options = (BuildOptions)cmd.build_options
+                    }
+                );
+            }
+#endif
+
+            return BuildPipeline.BuildPlayer(new BuildPlayerOptions
+                {
+                    scenes = UnityBuilder.GetActiveScenes(),
+                    locationPathName = locationPathName,
+                    target = target,
+                    options = (BuildOptions)cmd.build_options,
+                    extraScriptingDefines = cmd.defines != null && cmd.defines.Length > 0 ? cmd.defines : null
+                }
+            );
+        }
+
+        private static void ProcessRefreshCommand(CommandFile cmd)
+        {
+            AssetDatabase.Refresh();
+            WriteSuccessResult(cmd, "Refreshed the asset database");
+        }
+
+        private static void ProcessSwitchPlatformCommand(CommandFile cmd)
+        {
+            if (!Enum.TryParse(cmd.platform, out BuildTarget target))
+            {
+                WriteErrorResult(cmd, "INVALID_PLATFORM", $"Invalid platform: {cmd.platform}");
+                return;
+            }
+
+            if (EditorApplication.isPlaying)
+            {
+                WriteErrorResult(cmd, "IN_PLAY_MODE", "Unity editor is in Play Mode.");
+                return;
+            }
+
+            var originalPlatform = EditorUserBuildSettings.activeBuildTarget;
+            if (!TrySwitchPlatform(target, out var switchError, out var switchTime))
+            {
+                WriteResult(new ResultFile
+                    {
+                        command = cmd.command,
+                        uuid = cmd.uuid,
+                        status = "failed",
+                        error_code = "PLATFORM_SWITCH_FAILED",
+                        message = switchError
+                    }
+                );
+                return;
+            }
+
+            WriteSuccessResult(cmd,
+                originalPlatform == target
+                    ? $"{target} is already the active platform"
+                    : $"Switched from {originalPlatform} to {target} ({switchTime:0.0}s)"
+            );
+        }
+
+        /// <summary>
+        /// Invokes the static method named by <see cref="CommandFile.method"/>, which is the full name of the type
+        /// followed by the method name. The method either has no parameters or a single <c>string[]</c> parameter.
+        /// </summary>
+        private static void ProcessExecuteMethodCommand(CommandFile cmd)
+        {
+            if (EditorApplication.isCompiling)
+            {
+                WriteErrorResult(cmd, "COMPILING", "Unity editor is compiling.");
+                return;
+            }
+
+            var separator = cmd.method?.LastIndexOf('.') ?? -1;
+            if (separator <= 0)
+            {
+                WriteErrorResult(cmd, "INVALID_METHOD", $"Invalid method name: {cmd.method}. Use Type.Method.");
+                return;
+            }
+
+            var args = cmd.args ?? Array.Empty<string>();
+            var method = FindStaticMethod(cmd.method);
+            if (method == null)
+            {
+                WriteErrorResult(cmd,
+                    "METHOD_NOT_FOUND",
+                    $"Static method {cmd.method} without parameters or with a string[] parameter not found."
+                );
+                return;
+            }
+
+            object returnValue;
+            try
+            {
+                returnValue = method.Invoke(null, method.GetParameters().Length == 0 ? null : new object[] { args });
+            }
+            catch (TargetInvocationException e)
+            {
+                WriteResult(new ResultFile
+                    {
+                        command = cmd.command,
+                        uuid = cmd.uuid,
+                        status = "failed",
+                        error_code = "METHOD_FAILED",
+                        message = $"{cmd.method} failed: {e.InnerException?.Message ?? e.Message}"
+                    }
+                );
+                return;
+            }
+
+            WriteSuccessResult(cmd, returnValue?.ToString() ?? $"Executed {cmd.method}");
+        }
+
+        /// <summary>
+        /// Finds a static method without parameters or with a single string[] parameter by the full name of its
+        /// type followed by the method name.
+        /// </summary>
+        private static MethodInfo FindStaticMethod(string fullName)
+        {
+            var separator = fullName?.LastIndexOf('.') ?? -1;
+            if (separator <= 0)
+                return null;
+
+            var typeName = fullName.Substring(0, separator);
+            var methodName = fullName.Substring(separator + 1);
+
+            return AppDomain.CurrentDomain
+                .GetAssemblies()
+                .Select(assembly => assembly.GetType(typeName))
+                .Where(type => type != null)
+                .SelectMany(type => type.GetMethods(BindingFlags.Public | BindingFlags.NonPublic | BindingFlags.Static))
+                .Where(m => m.Name == methodName)
+                .FirstOrDefault(m => m.GetParameters().Length == 0
+                                     || m.GetParameters().Length == 1
+                                     && m.GetParameters()[0].ParameterType == typeof(string[])
+                );
+        }
+
+        private static void ProcessPlayModeCommand(CommandFile cmd, bool enter)
+        {
+            if (EditorApplication.isPlaying == enter)
+            {
+                WriteSuccessResult(cmd, enter ? "Already in Play Mode" : "Not in Play Mode");
+                return;
+            }
+
+            if (enter && EditorApplication.isCompiling)
+            {
+                WriteErrorResult(cmd, "COMPILING", "Unity editor is compiling.");
+                return;
+            }
+
+            // The result is written before the change takes effect on the next editor update,
+            // entering play mode can reload the domain.
+            WriteSuccessResult(cmd, enter ? "Entering Play Mode" : "Exiting Play Mode");
+            EditorApplication.isPlaying = enter;
+        }
+
+        private static void ProcessSaveScenesCommand(CommandFile cmd)
+        {
+            if (EditorApplication.isPlaying)
+            {
+                WriteErrorResult(cmd, "IN_PLAY_MODE", "Scenes cannot be saved in Play Mode.");
+                return;
+            }
+
+            if (!EditorSceneManager.SaveOpenScenes())
+            {
+                WriteResult(new ResultFile
+                    {
+                        command = cmd.command,
+                        uuid = cmd.uuid,
+                        status = "failed",
+                        error_code = "SAVE_FAILED",
+                        message = "Failed to save the open scenes."
+                    }
+                );
+                return;
+            }
+
+            WriteSuccessResult(cmd, $"Saved {EditorSceneManager.sceneCount} open scene(s)");
+        }
+
+        /// <summary>
+        /// Starts a test run with the Test Runner API. The result is written when the run finishes, see
+        /// <see cref="OnTestRunFinished"/>.
+        /// </summary>
+        private static void ProcessTestCommand(CommandFile cmd)
+        {
+#if UCOM_NO_TEST_FRAMEWORK
+            WriteErrorResult(cmd,
+                "TEST_FRAMEWORK_MISSING",
+                "Running tests requires the Test Framework package (com.unity.test-framework)."
+            );
+#else
+            if (EditorApplication.isPlaying)
+            {
+                WriteErrorResult(cmd, "IN_PLAY_MODE", "Unity editor is in Play Mode.");
+                return;
+            }
+
+            if (EditorApplication.isCompiling)
+            {
+                WriteErrorResult(cmd, "COMPILING", "Unity editor is compiling.");
+                return;
+            }
+
+            if (!string.IsNullOrEmpty(SessionState.GetString(PendingTestCommandKey, null)))
+            {
+                WriteErrorResult(cmd, "TESTS_RUNNING", "Unity editor is already running tests for ucom.");
+                return;
+            }
+
+            var filter = new Filter
+            {
+                categoryNames = NullIfEmpty(cmd.categories),
+                groupNames = NullIfEmpty(cmd.tests),
+                assemblyNames = NullIfEmpty(cmd.assemblies),
+            };
+
+            BuildTarget? targetPlatform = null;
+            switch (cmd.platform)
+            {
+                case "EditMode":
+                    filter.testMode = TestMode.EditMode;
+                    break;
+                case "PlayMode":
+                    filter.testMode = TestMode.PlayMode;
+                    break;
+                default:
+                    if (!Enum.TryParse(cmd.platform, out BuildTarget target))
+                    {
+                        WriteErrorResult(cmd, "INVALID_PLATFORM", $"Invalid test platform: {cmd.platform}");
+                        return;
+                    }
+
+                    // Play mode tests in a player for the target platform.
+                    filter.testMode = TestMode.PlayMode;
+                    targetPlatform = target;
+                    break;
+            }
+
+            // The command is kept in the session state, because running play mode tests reloads the domain.
+            SessionState.SetString(PendingTestCommandKey, JsonUtility.ToJson(cmd));
+            ScriptableObject.CreateInstance<TestRunnerApi>()
+                .Execute(new ExecutionSettings(filter) { targetPlatform = targetPlatform });
+#endif
+        }
+
+#if !UCOM_NO_TEST_FRAMEWORK
+        /// <summary>
+        /// Writes the NUnit XML and the result file of the test run that was started by ucom.
+        /// </summary>
+        internal static void OnTestRunFinished(ITestResultAdaptor result)
+        {
+            var pendingTestCommand = SessionState.GetString(PendingTestCommandKey, null);
+            if (string.IsNullOrEmpty(pendingTestCommand))
+                return; // Not started by ucom, or cancelled.
+
+            SessionState.EraseString(PendingTestCommandKey);
+            var cmd = JsonUtility.FromJson<CommandFile>(pendingTestCommand);
+
+            try
+            {
+                TestResultsWriter.Write(result, cmd.results_path);
+            }
+            catch (Exception e)
+            {
+                WriteResult(new ResultFile
+                    {
+                        command = cmd.command,
+                        uuid = cmd.uuid,
+                        status = "failed",
+                        error_code = "TEST_RESULTS_FAILED",
+                        message = $"Failed to write the test results: {e.Message}"
+                    }
+                );
+                return;
+            }
+
+            WriteSuccessResult(cmd,
+                $"{result.PassCount} passed; {result.FailCount} failed; {result.SkipCount} skipped"
+            );
+        }
+#endif
+
+        private static string[] NullIfEmpty(string[] values) => values != null && values.Length > 0 ? values : null;
+
+        private static void WriteSuccessResult(CommandFile cmd, string message)
+        {
+            WriteResult(new ResultFile
+                {
+                    command = cmd.command,
+                    uuid = cmd.uuid,
+                    status = "success",
+                    message = message
+                }
+            );
+        }
+
+        private static void WriteErrorResult(CommandFile cmd, string errorCode, string message)
         {
             var result = new ResultFile
             {
-                uuid = uuid,
+                command = cmd.command,
+                uuid = cmd.uuid,
                 status = "error",
                 error_code = errorCode,
                 message = message
@@ -902,14 +1689,28 @@ namespace Ucom
             try
             {
                 var json = JsonUtility.ToJson(result, true);
-                var resultPath = Path.Combine(ResultDir, $"build-{result.uuid}.json");
-                File.WriteAllText(resultPath, json);
+                var resultPath = Path.Combine(ResultDir, $"{result.command}-{result.uuid}.json");
+                WriteAllTextAtomically(resultPath, json);
             }
             catch (Exception e)
             {
                 Debug.LogError($"[Ucom] Failed to write result: {e.Message}");
             }
         }
+
+        /// <summary>
+        /// Writes the file under a temporary name and renames it, so ucom never reads a partially written result.
+        /// </summary>
+        private static void WriteAllTextAtomically(string path, string contents)
+        {
+            var tempPath = path + ".tmp";
+            File.WriteAllText(tempPath, contents);
+
+            if (File.Exists(path))
+                File.Delete(path);
+
+            File.Move(tempPath, path);
+        }
     }
 
     [Serializable]
@@ -920,9 +1721,19 @@ namespace Ucom
         public string uuid;
         public string timestamp;
         public string platform;
+        public string build_profile;
         public string output_path;
         public string log_path;
         public int build_options;
+        public string[] defines;
+        public string build_args;
+        public string build_function;
+        public string method;
+        public string[] args;
+        public string[] categories;
+        public string[] tests;
+        public string[] assemblies;
+        public string results_path;
         public bool development_build;
         public bool force_platform_switch;
         public bool force_play_mode_exit;
@@ -932,6 +1743,7 @@ namespace Ucom
     [SuppressMessage("ReSharper", "InconsistentNaming")]
     public class ResultFile
     {
+        public string command;
         public string uuid;
         public string status;
         public string message;
@@ -947,7 +1759,196 @@ namespace Ucom
         public ulong total_size;
         public int total_errors;
         public int total_warnings;
+        public List<ResultMessage> messages;
+    }
+
+    /// <summary>
+    /// An error or warning that is reported to ucom, with the location for compiler messages.
+    /// </summary>
+    [Serializable]
+    [SuppressMessage("ReSharper", "InconsistentNaming")]
+    public class ResultMessage : IEquatable<ResultMessage>
+    {
+        public string severity;
+        public string message;
+        public string code;
+        public string file;
+        public int line;
+        public int column;
+
+        public bool Equals(ResultMessage other) =>
+            other != null && severity == other.severity && message == other.message && code == other.code
+            && file == other.file && line == other.line && column == other.column;
+
+        public override bool Equals(object obj) => Equals(obj as ResultMessage);
+
+        public override int GetHashCode() => (message ?? "").GetHashCode() ^ line;
+    }
+
+    /// <summary>
+    /// A line in the progress file of a build, either a phase or a message.
+    /// </summary>
+    [Serializable]
+    [SuppressMessage("ReSharper", "InconsistentNaming")]
+    public class ProgressEvent
+    {
+        public string kind;
+        public string phase;
+        public float percentage;
+        public string severity;
+        public string message;
+        public string code;
+        public string file;
+        public int line;
+        public int column;
+
+        public static ProgressEvent ForMessage(ResultMessage message) => new ProgressEvent
+        {
+            kind = "message",
+            percentage = -1f,
+            severity = message.severity,
+            message = message.message,
+            code = message.code,
+            file = message.file,
+            line = message.line,
+            column = message.column
+        };
+    }
+
+    /// <summary>
+    /// Reports the phases of a build to the <see cref="EditorCommandWatcher"/>, which writes them to the
+    /// progress file when the build was started by ucom.
+    /// </summary>
+    internal class BuildProgressCallbacks : IPreprocessBuildWithReport, IProcessSceneWithReport,
+        IPostprocessBuildWithReport
+    {
+        // Runs after the other preprocessors, right before the scripts are compiled.
+        public int callbackOrder => int.MaxValue;
+
+        public void OnPreprocessBuild(BuildReport report) => EditorCommandWatcher.ReportPhase("Compiling scripts");
+
+        public void OnProcessScene(Scene scene, BuildReport report)
+        {
+            // The report is null when scenes are processed for play mode.
+            if (report != null)
+            {
+                EditorCommandWatcher.ReportSceneProcessed(scene);
+            }
+        }
+
+        public void OnPostprocessBuild(BuildReport report) => EditorCommandWatcher.ReportPhase("Postprocessing");
+    }
+
+#if !UCOM_NO_TEST_FRAMEWORK
+    /// <summary>
+    /// Passes the result of a test run to the <see cref="EditorCommandWatcher"/>.
+    /// </summary>
+    internal class TestRunCallbacks : ICallbacks
+    {
+        public void RunStarted(ITestAdaptor testsToRun) { }
+
+        public void RunFinished(ITestResultAdaptor result) => EditorCommandWatcher.OnTestRunFinished(result);
+
+        public void TestStarted(ITestAdaptor test) { }
+
+        public void TestFinished(ITestResultAdaptor result) { }
+    }
+
+    /// <summary>
+    /// Writes test results in the NUnit 3 XML format that Unity writes with <c>-testResults</c> in batch mode.
+    /// </summary>
+    internal static class TestResultsWriter
+    {
+        public static void Write(ITestResultAdaptor result, string path)
+        {
+            var nextId = 1000;
+            using (var writer = XmlWriter.Create(path, new XmlWriterSettings { Indent = true }))
+            {
+                writer.WriteStartDocument();
+                writer.WriteStartElement("test-run");
+                writer.WriteAttributeString("id", "2");
+                writer.WriteAttributeString("testcasecount", result.Test.TestCaseCount.ToString(CultureInfo.InvariantCulture));
+                WriteResultAttributes(writer, result);
+                writer.WriteAttributeString("engine-version", "3.5.0.0");
+                writer.WriteAttributeString("clr-version", Environment.Version.ToString());
+
+                // The root result is the test run itself, its children are the suites.
+                foreach (var child in result.Children)
+                    WriteTest(writer, child, ref nextId);
+
+                writer.WriteEndElement();
+                writer.WriteEndDocument();
+            }
+        }
+
+        private static void WriteTest(XmlWriter writer, ITestResultAdaptor result, ref int nextId)
+        {
+            var test = result.Test;
+            writer.WriteStartElement(test.IsSuite ? "test-suite" : "test-case");
+            if (test.IsSuite)
+                writer.WriteAttributeString("type", "TestSuite");
+
+            writer.WriteAttributeString("id", (nextId++).ToString(CultureInfo.InvariantCulture));
+            writer.WriteAttributeString("name", test.Name);
+            writer.WriteAttributeString("fullname", test.FullName);
+
+            if (test.IsSuite)
+            {
+                writer.WriteAttributeString("testcasecount", test.TestCaseCount.ToString(CultureInfo.InvariantCulture));
+            }
+            else
+            {
+                var className = test.FullName.EndsWith("." + test.Name)
+                    ? test.FullName.Substring(0, test.FullName.Length - test.Name.Length - 1)
+                    : test.FullName;
+                writer.WriteAttributeString("methodname", test.Name);
+                writer.WriteAttributeString("classname", className);
+                writer.WriteAttributeString("seed", "0");
+            }
+
+            writer.WriteAttributeString("runstate", test.RunState.ToString());
+            WriteResultAttributes(writer, result);
+
+            writer.WriteStartElement("properties");
+            writer.WriteEndElement();
+
+            if (result.TestStatus == TestStatus.Failed && !string.IsNullOrEmpty(result.Message))
+            {
+                writer.WriteStartElement("failure");
+                writer.WriteElementString("message", result.Message);
+                if (!string.IsNullOrEmpty(result.StackTrace))
+                    writer.WriteElementString("stack-trace", result.StackTrace);
+                writer.WriteEndElement();
+            }
+
+            if (!string.IsNullOrEmpty(result.Output))
+                writer.WriteElementString("output", result.Output);
+
+            if (test.IsSuite)
+            {
+                foreach (var child in result.Children)
+                    WriteTest(writer, child, ref nextId);
+            }
+
+            writer.WriteEndElement();
+        }
+
+        private static void WriteResultAttributes(XmlWriter writer, ITestResultAdaptor result)
+        {
+            var total = result.PassCount + result.FailCount + result.InconclusiveCount + result.SkipCount;
+            writer.WriteAttributeString("result", result.TestStatus.ToString());
+            writer.WriteAttributeString("start-time", result.StartTime.ToUniversalTime().ToString("u"));
+            writer.WriteAttributeString("end-time", result.EndTime.ToUniversalTime().ToString("u"));
+            writer.WriteAttributeString("duration", result.Duration.ToString("0.######", CultureInfo.InvariantCulture));
+            writer.WriteAttributeString("total", total.ToString(CultureInfo.InvariantCulture));
+            writer.WriteAttributeString("passed", result.PassCount.ToString(CultureInfo.InvariantCulture));
+            writer.WriteAttributeString("failed", result.FailCount.ToString(CultureInfo.InvariantCulture));
+            writer.WriteAttributeString("inconclusive", result.InconclusiveCount.ToString(CultureInfo.InvariantCulture));
+            writer.WriteAttributeString("skipped", result.SkipCount.ToString(CultureInfo.InvariantCulture));
+            writer.WriteAttributeString("asserts", result.AssertCount.ToString(CultureInfo.InvariantCulture));
+        }
     }
+#endif
 #endif // UNITY_EDITOR
 }
 #else